@@ -0,0 +1,416 @@
+use std::io::{self, Read, Write};
+
+use super::{QueueIndexRange, QueueWithIntervals};
+use crate::QueueValue;
+
+// Layout: version byte, varint amount of intervals, then for every interval a varint gap and a varint length,
+// followed by the little endian CRC-32 of all the previous bytes.
+// The gap of the first interval is its from_id (zigzag encoded for signed types),
+// the gap of the others is the amount of missing values after the previous to_id.
+// The length is to_id - from_id.
+pub const CODEC_VERSION: u8 = 1;
+
+// Varint of u128 takes up to 19 bytes
+const MAX_VARINT_LEN: usize = 19;
+
+// Upper limit of intervals allocated upfront, so a corrupted amount can not exhaust memory
+const MAX_PREALLOCATED_INTERVALS: u128 = 1024;
+
+impl<T: QueueValue> QueueWithIntervals<T> {
+    /// Encodes the queue into the compact binary format
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut result = Vec::new();
+        self.write_to(&mut result).unwrap();
+        result
+    }
+
+    /// Decodes the queue from the compact binary format
+    pub fn from_bytes(bytes: &[u8]) -> io::Result<Self> {
+        let mut reader = bytes;
+        let result = Self::read_from(&mut reader)?;
+
+        if !reader.is_empty() {
+            return Err(invalid_data("Unexpected bytes after the checksum"));
+        }
+
+        Ok(result)
+    }
+
+    /// Writes the queue in the compact binary format
+    pub fn write_to(&self, writer: impl Write) -> io::Result<()> {
+        let mut writer = ChecksumWriter {
+            inner: writer,
+            crc: Crc32::new(),
+        };
+
+        writer.write_all(&[CODEC_VERSION])?;
+
+        let intervals = self.get_non_empty_intervals();
+        write_varint(&mut writer, intervals.len() as u128)?;
+
+        let mut prev_to_id = None;
+
+        for interval in intervals {
+            let gap = match prev_to_id {
+                Some(prev_to_id) => T::distance(prev_to_id, interval.from_id) - 1,
+                None => zigzag_encode(interval.from_id),
+            };
+
+            write_varint(&mut writer, gap)?;
+            write_varint(&mut writer, T::distance(interval.from_id, interval.to_id))?;
+
+            prev_to_id = Some(interval.to_id);
+        }
+
+        let crc = writer.crc.finish();
+        writer.inner.write_all(&crc.to_le_bytes())
+    }
+
+    /// Reads the queue written by write_to. Rejects unknown versions, corrupted data and non-canonical intervals
+    pub fn read_from(reader: impl Read) -> io::Result<Self> {
+        let mut reader = ChecksumReader {
+            inner: reader,
+            crc: Crc32::new(),
+        };
+
+        let mut version = [0u8; 1];
+        reader.read_exact(&mut version)?;
+
+        if version[0] != CODEC_VERSION {
+            return Err(invalid_data(format!(
+                "Unsupported codec version {}",
+                version[0]
+            )));
+        }
+
+        let intervals_amount = read_varint(&mut reader)?;
+        let mut intervals =
+            Vec::with_capacity(intervals_amount.min(MAX_PREALLOCATED_INTERVALS) as usize);
+
+        let mut prev_to_id: Option<T> = None;
+
+        for _ in 0..intervals_amount {
+            let gap = read_varint(&mut reader)?;
+            let length = read_varint(&mut reader)?;
+
+            let from_id = match prev_to_id {
+                Some(prev_to_id) => {
+                    if gap == 0 {
+                        return Err(invalid_data("Interval is adjacent to the previous one"));
+                    }
+
+                    gap.checked_add(1)
+                        .and_then(|distance| checked_add_distance(prev_to_id, distance))
+                }
+                None => zigzag_decode(gap),
+            }
+            .ok_or_else(|| invalid_data("Interval starts out of the range of the type"))?;
+
+            let to_id = checked_add_distance(from_id, length)
+                .ok_or_else(|| invalid_data("Interval ends out of the range of the type"))?;
+
+            intervals.push(QueueIndexRange { from_id, to_id });
+            prev_to_id = Some(to_id);
+        }
+
+        let crc = reader.crc.finish();
+
+        let mut expected_crc = [0u8; 4];
+        reader.inner.read_exact(&mut expected_crc)?;
+
+        if u32::from_le_bytes(expected_crc) != crc {
+            return Err(invalid_data("Checksum mismatch"));
+        }
+
+        Ok(Self::from_sorted_intervals(intervals))
+    }
+}
+
+fn invalid_data(message: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.into())
+}
+
+fn checked_add_distance<T: QueueValue>(value: T, distance: u128) -> Option<T> {
+    if T::distance(value, T::max_value()) < distance {
+        return None;
+    }
+
+    Some(value.add_distance(distance))
+}
+
+// Signed values are mapped to 0, -1, 1, -2, 2... so small negative values stay short
+fn zigzag_encode<T: QueueValue>(value: T) -> u128 {
+    if T::min_value() == T::zero() {
+        return T::distance(T::zero(), value);
+    }
+
+    if value < T::zero() {
+        // Written as 2 * (distance - 1) + 1 so i128::MIN does not overflow
+        (T::distance(value, T::zero()) - 1) * 2 + 1
+    } else {
+        T::distance(T::zero(), value) * 2
+    }
+}
+
+fn zigzag_decode<T: QueueValue>(value: u128) -> Option<T> {
+    if T::min_value() == T::zero() {
+        return checked_add_distance(T::zero(), value);
+    }
+
+    if value.is_multiple_of(2) {
+        return checked_add_distance(T::zero(), value / 2);
+    }
+
+    let below_zero = value / 2 + 1;
+    let min_below_zero = T::distance(T::min_value(), T::zero());
+
+    if below_zero > min_below_zero {
+        return None;
+    }
+
+    Some(T::min_value().add_distance(min_below_zero - below_zero))
+}
+
+fn write_varint(writer: &mut impl Write, mut value: u128) -> io::Result<()> {
+    let mut buffer = [0u8; MAX_VARINT_LEN];
+    let mut len = 0;
+
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+
+        if value == 0 {
+            buffer[len] = byte;
+            len += 1;
+            break;
+        }
+
+        buffer[len] = byte | 0x80;
+        len += 1;
+    }
+
+    writer.write_all(&buffer[..len])
+}
+
+fn read_varint(reader: &mut impl Read) -> io::Result<u128> {
+    let mut result = 0u128;
+
+    for index in 0..MAX_VARINT_LEN {
+        let mut byte = [0u8; 1];
+        reader.read_exact(&mut byte)?;
+
+        let bits = (byte[0] & 0x7f) as u128;
+        let shift = index * 7;
+
+        // The last byte carries only the two highest bits of u128
+        if index == MAX_VARINT_LEN - 1 && bits > 0b11 {
+            return Err(invalid_data("Varint overflows u128"));
+        }
+
+        result |= bits << shift;
+
+        if byte[0] & 0x80 == 0 {
+            return Ok(result);
+        }
+    }
+
+    Err(invalid_data("Varint is too long"))
+}
+
+struct ChecksumWriter<W: Write> {
+    inner: W,
+    crc: Crc32,
+}
+
+impl<W: Write> Write for ChecksumWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.crc.update(&buf[..written]);
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+struct ChecksumReader<R: Read> {
+    inner: R,
+    crc: Crc32,
+}
+
+impl<R: Read> Read for ChecksumReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.crc.update(&buf[..read]);
+        Ok(read)
+    }
+}
+
+// CRC-32 (IEEE 802.3)
+struct Crc32 {
+    value: u32,
+}
+
+const CRC32_TABLE: [u32; 256] = build_crc32_table();
+
+const fn build_crc32_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut index = 0;
+
+    while index < 256 {
+        let mut value = index as u32;
+        let mut bit = 0;
+
+        while bit < 8 {
+            value = if value & 1 == 1 {
+                (value >> 1) ^ 0xedb8_8320
+            } else {
+                value >> 1
+            };
+            bit += 1;
+        }
+
+        table[index] = value;
+        index += 1;
+    }
+
+    table
+}
+
+impl Crc32 {
+    fn new() -> Self {
+        Self { value: 0xffff_ffff }
+    }
+
+    fn update(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.value =
+                CRC32_TABLE[((self.value ^ *byte as u32) & 0xff) as usize] ^ (self.value >> 8);
+        }
+    }
+
+    fn finish(&self) -> u32 {
+        self.value ^ 0xffff_ffff
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CODEC_VERSION, Crc32};
+    use crate::{QueueIndexRange, QueueWithIntervals};
+
+    fn intervals<T: crate::QueueValue>(queue: &QueueWithIntervals<T>) -> Vec<(T, T)> {
+        queue
+            .intervals_iter()
+            .map(|itm| (itm.from_id, itm.to_id))
+            .collect()
+    }
+
+    macro_rules! test_round_trip {
+        ($($name:ident: $t:ty),*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let empty = QueueWithIntervals::<$t>::new();
+                    let decoded = QueueWithIntervals::<$t>::from_bytes(&empty.to_bytes()).unwrap();
+                    assert!(decoded.is_empty());
+
+                    let full = QueueWithIntervals::<$t>::from_single_interval(<$t>::MIN, <$t>::MAX);
+                    let decoded = QueueWithIntervals::<$t>::from_bytes(&full.to_bytes()).unwrap();
+                    assert_eq!(intervals(&full), intervals(&decoded));
+                    assert_eq!(full.len_u128(), decoded.len_u128());
+
+                    let mut queue = QueueWithIntervals::<$t>::new();
+                    queue.enqueue(<$t>::MIN);
+                    queue.enqueue_range(QueueIndexRange::restore(<$t>::MIN + 2, <$t>::MIN + 5));
+                    queue.enqueue_range(QueueIndexRange::restore(0, 3));
+                    queue.enqueue_range(QueueIndexRange::restore(10, 10));
+                    queue.enqueue_range(QueueIndexRange::restore(<$t>::MAX - 3, <$t>::MAX));
+
+                    let mut bytes = Vec::new();
+                    queue.write_to(&mut bytes).unwrap();
+                    let decoded = QueueWithIntervals::<$t>::read_from(bytes.as_slice()).unwrap();
+
+                    assert_eq!(intervals(&queue), intervals(&decoded));
+                    assert_eq!(queue.len_u128(), decoded.len_u128());
+                }
+            )*
+        };
+    }
+
+    test_round_trip!(
+        round_trip_i8: i8,
+        round_trip_i16: i16,
+        round_trip_i32: i32,
+        round_trip_i64: i64,
+        round_trip_i128: i128,
+        round_trip_isize: isize,
+        round_trip_u8: u8,
+        round_trip_u16: u16,
+        round_trip_u32: u32,
+        round_trip_u64: u64,
+        round_trip_u128: u128,
+        round_trip_usize: usize
+    );
+
+    #[test]
+    fn test_encoding_is_compact() {
+        let mut queue = QueueWithIntervals::<i64>::new();
+        queue.enqueue_range(QueueIndexRange::restore(-1, 1));
+        queue.enqueue_range(QueueIndexRange::restore(5, 5));
+
+        // version, amount, zigzag(-1) and length 2, gap 3 and length 0, crc
+        let bytes = queue.to_bytes();
+        assert_eq!(&bytes[..6], &[CODEC_VERSION, 2, 1, 2, 3, 0]);
+        assert_eq!(bytes.len(), 10);
+
+        let mut queue = QueueWithIntervals::<u64>::new();
+        for value in 0..1000u64 {
+            queue.enqueue(1_000_000_000 + value * 10);
+        }
+
+        assert!(queue.to_bytes().len() < 2 * 1000 + 16);
+    }
+
+    #[test]
+    fn test_crc32() {
+        let mut crc = Crc32::new();
+        crc.update(b"123456789");
+        assert_eq!(crc.finish(), 0xcbf4_3926);
+    }
+
+    #[test]
+    fn test_corrupted_data_is_rejected() {
+        let mut queue = QueueWithIntervals::<i64>::new();
+        queue.enqueue_range(QueueIndexRange::restore(10, 20));
+        queue.enqueue_range(QueueIndexRange::restore(30, 40));
+
+        let bytes = queue.to_bytes();
+
+        let mut corrupted = bytes.clone();
+        corrupted[3] ^= 1;
+        assert!(QueueWithIntervals::<i64>::from_bytes(&corrupted).is_err());
+
+        let mut wrong_version = bytes.clone();
+        wrong_version[0] = CODEC_VERSION + 1;
+        assert!(QueueWithIntervals::<i64>::from_bytes(&wrong_version).is_err());
+
+        for len in 0..bytes.len() {
+            assert!(QueueWithIntervals::<i64>::from_bytes(&bytes[..len]).is_err());
+        }
+
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert!(QueueWithIntervals::<i64>::from_bytes(&trailing).is_err());
+    }
+
+    #[test]
+    fn test_values_out_of_type_range_are_rejected() {
+        let mut queue = QueueWithIntervals::<i64>::new();
+        queue.enqueue_range(QueueIndexRange::restore(-10, 300));
+
+        assert!(QueueWithIntervals::<u8>::from_bytes(&queue.to_bytes()).is_err());
+        assert!(QueueWithIntervals::<i16>::from_bytes(&queue.to_bytes()).is_ok());
+    }
+}
@@ -0,0 +1,315 @@
+use std::{
+    cmp::Ordering,
+    hash::{Hash, Hasher},
+    iter::Peekable,
+    slice::Iter,
+};
+
+use super::{QueueIndexRange, QueueWithIntervals};
+use crate::QueueValue;
+
+// Ranges and queues are compared by the values they hold. All empty ranges are equal
+// no matter which ids the empty placeholder keeps.
+// Ord is lexicographic by values in ascending order, the same way BTreeSet compares.
+
+impl<T: QueueValue> PartialEq for QueueIndexRange<T> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self.is_empty(), other.is_empty()) {
+            (true, true) => true,
+            (false, false) => self.from_id == other.from_id && self.to_id == other.to_id,
+            _ => false,
+        }
+    }
+}
+
+impl<T: QueueValue> Eq for QueueIndexRange<T> {}
+
+impl<T: QueueValue + Hash> Hash for QueueIndexRange<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        if self.is_empty() {
+            state.write_u8(0);
+            return;
+        }
+
+        state.write_u8(1);
+        self.from_id.hash(state);
+        self.to_id.hash(state);
+    }
+}
+
+impl<T: QueueValue> PartialOrd for QueueIndexRange<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: QueueValue> Ord for QueueIndexRange<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.is_empty(), other.is_empty()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+            // The shorter of two ranges starting at the same value is a prefix of the longer one
+            (false, false) => self
+                .from_id
+                .cmp(&other.from_id)
+                .then(self.to_id.cmp(&other.to_id)),
+        }
+    }
+}
+
+// restore and reset only sort the intervals they get, so the same values may be stored as
+// overlapping or adjacent intervals. Queues are compared by intervals joined on the fly.
+struct JoinedIntervals<'s, T: QueueValue> {
+    intervals: Peekable<Iter<'s, QueueIndexRange<T>>>,
+}
+
+impl<'s, T: QueueValue> JoinedIntervals<'s, T> {
+    fn new(queue: &'s QueueWithIntervals<T>) -> Self {
+        Self {
+            intervals: queue.get_non_empty_intervals().iter().peekable(),
+        }
+    }
+}
+
+impl<T: QueueValue> Iterator for JoinedIntervals<'_, T> {
+    type Item = QueueIndexRange<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut result = self.intervals.find(|itm| !itm.is_empty())?.clone();
+
+        while let Some(interval) = self.intervals.next_if(|itm| {
+            itm.is_empty()
+                || match result.to_id.checked_add_one() {
+                    Some(to_plus_one) => itm.from_id <= to_plus_one,
+                    None => true,
+                }
+        }) {
+            if !interval.is_empty() && interval.to_id > result.to_id {
+                result.to_id = interval.to_id;
+            }
+        }
+
+        Some(result)
+    }
+}
+
+impl<T: QueueValue> PartialEq for QueueWithIntervals<T> {
+    fn eq(&self, other: &Self) -> bool {
+        JoinedIntervals::new(self).eq(JoinedIntervals::new(other))
+    }
+}
+
+impl<T: QueueValue> Eq for QueueWithIntervals<T> {}
+
+impl<T: QueueValue + Hash> Hash for QueueWithIntervals<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let mut intervals_count = 0usize;
+
+        for interval in JoinedIntervals::new(self) {
+            interval.hash(state);
+            intervals_count += 1;
+        }
+
+        state.write_usize(intervals_count);
+    }
+}
+
+impl<T: QueueValue> PartialOrd for QueueWithIntervals<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: QueueValue> Ord for QueueWithIntervals<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let mut left = JoinedIntervals::new(self).peekable();
+        let mut right = JoinedIntervals::new(other).peekable();
+
+        loop {
+            let (left_interval, right_interval) = match (left.next(), right.next()) {
+                (None, None) => return Ordering::Equal,
+                (None, Some(_)) => return Ordering::Less,
+                (Some(_), None) => return Ordering::Greater,
+                (Some(left_interval), Some(right_interval)) => (left_interval, right_interval),
+            };
+
+            if left_interval.from_id != right_interval.from_id {
+                return left_interval.from_id.cmp(&right_interval.from_id);
+            }
+
+            // After the shorter interval ends, the longer one continues with the next value,
+            // while the other queue either ends or jumps over a gap to a bigger value
+            match left_interval.to_id.cmp(&right_interval.to_id) {
+                Ordering::Equal => {}
+                Ordering::Less => {
+                    return match left.peek() {
+                        Some(_) => Ordering::Greater,
+                        None => Ordering::Less,
+                    };
+                }
+                Ordering::Greater => {
+                    return match right.peek() {
+                        Some(_) => Ordering::Less,
+                        None => Ordering::Greater,
+                    };
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::{BTreeSet, HashSet},
+        hash::{BuildHasher, RandomState},
+    };
+
+    use crate::{QueueIndexRange, QueueWithIntervals};
+
+    fn queue(values: &[i64]) -> QueueWithIntervals<i64> {
+        let mut result = QueueWithIntervals::new();
+
+        for value in values {
+            result.enqueue(*value);
+        }
+
+        result
+    }
+
+    #[test]
+    fn test_range_equality() {
+        assert_eq!(
+            QueueIndexRange::restore(1, 5),
+            QueueIndexRange::restore(1, 5)
+        );
+        assert_ne!(
+            QueueIndexRange::restore(1, 5),
+            QueueIndexRange::restore(1, 6)
+        );
+        assert_eq!(
+            QueueIndexRange::new_empty(0),
+            QueueIndexRange::new_empty(100)
+        );
+        assert_ne!(
+            QueueIndexRange::new_empty(5),
+            QueueIndexRange::restore(5, 5)
+        );
+
+        let set: HashSet<_> = [
+            QueueIndexRange::new_empty(0),
+            QueueIndexRange::new_empty(10),
+            QueueIndexRange::restore(1, 2),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(set.len(), 2);
+    }
+
+    #[test]
+    fn test_range_ordering() {
+        assert!(QueueIndexRange::new_empty(100) < QueueIndexRange::restore(i64::MIN, i64::MIN));
+        assert!(QueueIndexRange::restore(1, 3) < QueueIndexRange::restore(1, 5));
+        assert!(QueueIndexRange::restore(1, 5) < QueueIndexRange::restore(2, 3));
+    }
+
+    #[test]
+    fn test_empty_queues_are_equal() {
+        let mut cleaned = queue(&[10, 11, 12]);
+        cleaned.clean();
+
+        let mut dequeued = queue(&[-5]);
+        dequeued.dequeue();
+
+        assert_eq!(cleaned, QueueWithIntervals::new());
+        assert_eq!(cleaned, dequeued);
+
+        // Queues keep a lazily built cache inside, so they are hashed directly instead of being put into a HashSet
+        let state = RandomState::new();
+        assert_eq!(state.hash_one(&cleaned), state.hash_one(&dequeued));
+        assert_eq!(
+            state.hash_one(&cleaned),
+            state.hash_one(QueueWithIntervals::<i64>::new())
+        );
+    }
+
+    #[test]
+    fn test_queue_equality_ignores_history() {
+        let mut left = queue(&[1, 2, 3, 10]);
+        left.remove(2).unwrap();
+
+        let mut right = QueueWithIntervals::new();
+        right.enqueue_range(QueueIndexRange::restore(10, 10));
+        right.enqueue_range(QueueIndexRange::restore(1, 1));
+        right.enqueue(3);
+
+        assert_eq!(left, right);
+
+        right.enqueue(4);
+        assert_ne!(left, right);
+    }
+
+    #[test]
+    fn test_queue_equality_joins_restored_intervals() {
+        let adjacent = QueueWithIntervals::restore(vec![
+            QueueIndexRange::restore(3, 4),
+            QueueIndexRange::restore(1, 2),
+        ]);
+        let overlapping = QueueWithIntervals::restore(vec![
+            QueueIndexRange::restore(1, 3),
+            QueueIndexRange::restore(2, 4),
+            QueueIndexRange::restore(4, 4),
+        ]);
+        let single = QueueWithIntervals::from_single_interval(1, 4);
+
+        assert_eq!(adjacent, single);
+        assert_eq!(overlapping, single);
+        assert_eq!(adjacent.cmp(&single), std::cmp::Ordering::Equal);
+        assert_eq!(overlapping.cmp(&single), std::cmp::Ordering::Equal);
+
+        let state = RandomState::new();
+        assert_eq!(state.hash_one(&adjacent), state.hash_one(&single));
+        assert_eq!(state.hash_one(&overlapping), state.hash_one(&single));
+
+        assert!(adjacent < queue(&[1, 2, 3, 4, 5]));
+        assert!(adjacent > queue(&[1, 2, 3]));
+        assert!(adjacent < queue(&[1, 2, 3, 6]));
+    }
+
+    #[test]
+    fn test_queue_ordering_matches_btree_set() {
+        let cases: Vec<Vec<i64>> = vec![
+            vec![],
+            vec![1],
+            vec![1, 2],
+            vec![1, 2, 3],
+            vec![1, 3],
+            vec![1, 2, 4],
+            vec![1, 2, 3, 5],
+            vec![2],
+            vec![2, 3],
+            vec![-1, 7],
+            vec![1, 2, 5, 6],
+            vec![1, 2, 5],
+        ];
+
+        for left in &cases {
+            for right in &cases {
+                let expected = left
+                    .iter()
+                    .collect::<BTreeSet<_>>()
+                    .cmp(&right.iter().collect::<BTreeSet<_>>());
+
+                assert_eq!(
+                    expected,
+                    queue(left).cmp(&queue(right)),
+                    "{:?} vs {:?}",
+                    left,
+                    right
+                );
+            }
+        }
+    }
+}
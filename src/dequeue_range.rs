@@ -0,0 +1,186 @@
+use super::{QueueIndexRange, QueueWithIntervals};
+use crate::QueueValue;
+
+impl<T: QueueValue> QueueWithIntervals<T> {
+    /// Dequeues up to max_count values from the head of the first interval
+    pub fn dequeue_range(&mut self, max_count: usize) -> Option<QueueIndexRange<T>> {
+        self.dequeue_from_head(max_count, 1).pop()
+    }
+
+    /// Dequeues up to max_count values from the head of the queue. Values may come from several intervals
+    pub fn dequeue_batch(&mut self, max_count: usize) -> Vec<QueueIndexRange<T>> {
+        self.dequeue_from_head(max_count, usize::MAX)
+    }
+
+    /// Dequeues up to max_count biggest values from the tail of the last interval
+    pub fn dequeue_range_back(&mut self, max_count: usize) -> Option<QueueIndexRange<T>> {
+        if max_count == 0 || self.is_empty() {
+            return None;
+        }
+
+        let last_index = self.intervals.len() - 1;
+        let interval = self.intervals.get_mut(last_index).unwrap();
+        let count = interval.len_u128();
+        let max_count = max_count as u128;
+
+        let result = if count <= max_count {
+            let result = interval.clone();
+            self.remove_interval(last_index);
+            self.values_count = self.values_count.wrapping_sub(count);
+            result
+        } else {
+            let distance = T::distance(interval.from_id, interval.to_id);
+            let from_id = interval.from_id.add_distance(distance - (max_count - 1));
+            let result = QueueIndexRange::restore(from_id, interval.to_id);
+            // The interval starts before from_id, so there is a previous value
+            interval.to_id = from_id.checked_sub_one().unwrap();
+            self.values_count = self.values_count.wrapping_sub(max_count);
+            result
+        };
+
+        self.after_mutation("dequeue_range_back");
+
+        Some(result)
+    }
+
+    fn dequeue_from_head(
+        &mut self,
+        max_count: usize,
+        max_intervals: usize,
+    ) -> Vec<QueueIndexRange<T>> {
+        if max_count == 0 || self.is_empty() {
+            return vec![];
+        }
+
+        let mut result = Vec::new();
+        let mut remaining = max_count as u128;
+        let mut whole_intervals = 0;
+
+        for interval in self.intervals.iter_mut().take(max_intervals) {
+            let count = interval.len_u128();
+
+            if count <= remaining {
+                result.push(interval.clone());
+                remaining -= count;
+                whole_intervals += 1;
+
+                if remaining == 0 {
+                    break;
+                }
+
+                continue;
+            }
+
+            let to_id = interval.from_id.add_distance(remaining - 1);
+            result.push(QueueIndexRange::restore(interval.from_id, to_id));
+            // The interval goes on after to_id, so there is a next value
+            interval.from_id = to_id.checked_add_one().unwrap();
+            remaining = 0;
+            break;
+        }
+
+        self.remove_head_intervals(whole_intervals);
+
+        self.values_count = self
+            .values_count
+            .wrapping_sub(max_count as u128 - remaining);
+        self.after_mutation("dequeue_batch");
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{QueueIndexRange, QueueWithIntervals};
+
+    fn to_pairs(ranges: &[QueueIndexRange<i64>]) -> Vec<(i64, i64)> {
+        ranges.iter().map(|itm| (itm.from_id, itm.to_id)).collect()
+    }
+
+    #[test]
+    fn test_dequeue_range() {
+        let mut queue: QueueWithIntervals = "1-10,20-25".parse().unwrap();
+
+        let range = queue.dequeue_range(4).unwrap();
+        assert_eq!((range.from_id, range.to_id), (1, 4));
+        assert_eq!(queue.to_string(), "5-10,20-25");
+        assert_eq!(queue.len(), 12);
+
+        // Stops at the end of the first interval
+        let range = queue.dequeue_range(100).unwrap();
+        assert_eq!((range.from_id, range.to_id), (5, 10));
+        assert_eq!(queue.to_string(), "20-25");
+
+        assert!(queue.dequeue_range(0).is_none());
+
+        let range = queue.dequeue_range(6).unwrap();
+        assert_eq!((range.from_id, range.to_id), (20, 25));
+        assert!(queue.is_empty());
+        assert_eq!(queue.len(), 0);
+        assert!(queue.dequeue_range(1).is_none());
+
+        queue.enqueue(7);
+        assert_eq!(queue.to_string(), "7");
+    }
+
+    #[test]
+    fn test_dequeue_batch() {
+        let mut queue: QueueWithIntervals = "1-3,5,7-10,20-25".parse().unwrap();
+
+        let batch = queue.dequeue_batch(6);
+        assert_eq!(to_pairs(&batch), vec![(1, 3), (5, 5), (7, 8)]);
+        assert_eq!(queue.to_string(), "9-10,20-25");
+        assert_eq!(queue.len(), 8);
+
+        let batch = queue.dequeue_batch(8);
+        assert_eq!(to_pairs(&batch), vec![(9, 10), (20, 25)]);
+        assert!(queue.is_empty());
+
+        assert!(queue.dequeue_batch(8).is_empty());
+        assert!(queue.validate().is_ok());
+    }
+
+    #[test]
+    fn test_dequeue_range_back() {
+        let mut queue: QueueWithIntervals = "1-10,20-25".parse().unwrap();
+
+        let range = queue.dequeue_range_back(4).unwrap();
+        assert_eq!((range.from_id, range.to_id), (22, 25));
+        assert_eq!(queue.to_string(), "1-10,20-21");
+        assert_eq!(queue.len(), 12);
+
+        // Stops at the start of the last interval
+        let range = queue.dequeue_range_back(100).unwrap();
+        assert_eq!((range.from_id, range.to_id), (20, 21));
+        assert_eq!(queue.to_string(), "1-10");
+
+        assert!(queue.dequeue_range_back(0).is_none());
+
+        let range = queue.dequeue_range_back(10).unwrap();
+        assert_eq!((range.from_id, range.to_id), (1, 10));
+        assert!(queue.is_empty());
+        assert!(queue.dequeue_range_back(1).is_none());
+
+        let mut queue = QueueWithIntervals::<i128>::from_single_interval(i128::MIN, i128::MAX);
+        let range = queue.dequeue_range_back(3).unwrap();
+        assert_eq!((range.from_id, range.to_id), (i128::MAX - 2, i128::MAX));
+        assert_eq!(queue.get_max_id(), Some(i128::MAX - 3));
+        assert!(queue.validate().is_ok());
+    }
+
+    #[test]
+    fn test_dequeue_batch_at_type_edges() {
+        let mut queue = QueueWithIntervals::<u8>::from_single_interval(250, u8::MAX);
+        let batch = queue.dequeue_batch(100);
+        assert_eq!(batch.len(), 1);
+        assert_eq!((batch[0].from_id, batch[0].to_id), (250, u8::MAX));
+        assert!(queue.is_empty());
+
+        let mut queue = QueueWithIntervals::<u128>::from_single_interval(0, u128::MAX);
+        let range = queue.dequeue_range(usize::MAX).unwrap();
+        assert_eq!(range.to_id, usize::MAX as u128 - 1);
+        assert_eq!(queue.get_min_id(), Some(usize::MAX as u128));
+        assert!(queue.validate().is_ok());
+    }
+}
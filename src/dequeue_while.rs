@@ -0,0 +1,133 @@
+use super::{QueueIndexRange, QueueWithIntervals};
+use crate::QueueValue;
+
+impl<T: QueueValue> QueueWithIntervals<T> {
+    /// Dequeues every value which is less or equal to the value and returns them as a queue
+    pub fn dequeue_up_to(&mut self, value: T) -> Self {
+        if self.is_empty() {
+            return Self::new();
+        }
+
+        let whole_intervals = self.intervals.partition_point(|itm| itm.to_id <= value);
+        let mut result = self.intervals[..whole_intervals].to_vec();
+
+        if let Some(interval) = self.intervals.get_mut(whole_intervals)
+            && interval.from_id <= value
+        {
+            result.push(QueueIndexRange::restore(interval.from_id, value));
+            // The interval ends after the value, so there is a next value
+            interval.from_id = value.checked_add_one().unwrap();
+        }
+
+        if result.is_empty() {
+            return Self::new();
+        }
+
+        self.remove_head_intervals(whole_intervals);
+
+        let result = Self::from_sorted_intervals(result);
+        self.values_count = self.values_count.wrapping_sub(result.values_count);
+        self.after_mutation("dequeue_up_to");
+
+        result
+    }
+
+    /// Dequeues values from the head while the predicate holds and returns them as a queue.
+    /// The predicate is called value by value, but intervals are removed at once
+    pub fn dequeue_while(&mut self, mut predicate: impl FnMut(T) -> bool) -> Self {
+        let mut last_value = None;
+
+        'intervals: for interval in self.get_non_empty_intervals() {
+            let mut value = interval.from_id;
+
+            loop {
+                if !predicate(value) {
+                    break 'intervals;
+                }
+
+                last_value = Some(value);
+
+                if value == interval.to_id {
+                    break;
+                }
+
+                value = value.checked_add_one().unwrap();
+            }
+        }
+
+        match last_value {
+            Some(value) => self.dequeue_up_to(value),
+            None => Self::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::QueueWithIntervals;
+
+    #[test]
+    fn test_dequeue_up_to() {
+        let mut queue: QueueWithIntervals = "1-10,20-25,30".parse().unwrap();
+
+        let dequeued = queue.dequeue_up_to(0);
+        assert!(dequeued.is_empty());
+        assert_eq!(queue.len(), 17);
+
+        let dequeued = queue.dequeue_up_to(5);
+        assert_eq!(dequeued.to_string(), "1-5");
+        assert_eq!(queue.to_string(), "6-10,20-25,30");
+        assert_eq!(queue.len(), 12);
+
+        let dequeued = queue.dequeue_up_to(15);
+        assert_eq!(dequeued.to_string(), "6-10");
+        assert_eq!(queue.to_string(), "20-25,30");
+
+        let dequeued = queue.dequeue_up_to(25);
+        assert_eq!(dequeued.to_string(), "20-25");
+        assert_eq!(queue.to_string(), "30");
+
+        let dequeued = queue.dequeue_up_to(i64::MAX);
+        assert_eq!(dequeued.to_string(), "30");
+        assert!(queue.is_empty());
+        assert_eq!(queue.len(), 0);
+
+        assert!(queue.dequeue_up_to(i64::MAX).is_empty());
+        assert!(queue.validate().is_ok());
+    }
+
+    #[test]
+    fn test_dequeue_up_to_at_type_edges() {
+        let mut queue = QueueWithIntervals::<u8>::from_single_interval(0, u8::MAX);
+
+        let dequeued = queue.dequeue_up_to(0);
+        assert_eq!(dequeued.to_string(), "0");
+        assert_eq!(queue.to_string(), "1-255");
+
+        let dequeued = queue.dequeue_up_to(u8::MAX);
+        assert_eq!(dequeued.len(), 255);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_dequeue_while() {
+        let mut queue: QueueWithIntervals = "1-10,20-25".parse().unwrap();
+
+        let dequeued = queue.dequeue_while(|value| value < 8);
+        assert_eq!(dequeued.to_string(), "1-7");
+        assert_eq!(queue.to_string(), "8-10,20-25");
+
+        // Stops at the first value failing the predicate even if later values pass it
+        let dequeued = queue.dequeue_while(|value| value != 9);
+        assert_eq!(dequeued.to_string(), "8");
+        assert_eq!(queue.to_string(), "9-10,20-25");
+
+        let dequeued = queue.dequeue_while(|value| value > 100);
+        assert!(dequeued.is_empty());
+        assert_eq!(queue.len(), 8);
+
+        let dequeued = queue.dequeue_while(|_| true);
+        assert_eq!(dequeued.to_string(), "9-10,20-25");
+        assert!(queue.is_empty());
+    }
+}
@@ -1,47 +1,83 @@
-use super::{IndexRange, QueueIndexRange, QueueWithIntervals};
+use super::{IndexRange, QueueIndexRange, QueueWithIntervals, QueueWithIntervalsError};
 use crate::QueueValue;
 
 impl<T: QueueValue> QueueWithIntervals<T> {
-    pub fn enqueue_range(&mut self, range_to_insert: QueueIndexRange<T>) {
+    pub fn enqueue_range(&mut self, range_to_insert: impl Into<QueueIndexRange<T>>) {
+        if let Err(err) = self.try_enqueue_range(range_to_insert) {
+            panic!("Can not enqueue range: {:?}", err);
+        }
+    }
+
+    /// Enqueues the range. Returns true if at least one value of the range was not in the queue
+    pub fn try_enqueue_range(
+        &mut self,
+        range_to_insert: impl Into<QueueIndexRange<T>>,
+    ) -> Result<bool, QueueWithIntervalsError> {
+        let added = self.try_enqueue_range_counting(range_to_insert.into())?;
+        Ok(added.is_some())
+    }
+
+    // Returns the amount of values which were not in the queue, or None if there were none.
+    // Some(0) means all 2^128 values of i128/u128 were added
+    pub(crate) fn try_enqueue_range_counting(
+        &mut self,
+        range_to_insert: QueueIndexRange<T>,
+    ) -> Result<Option<u128>, QueueWithIntervalsError> {
         if range_to_insert.is_empty() {
-            return;
+            return Ok(None);
         }
 
         if self.is_empty() {
             let first = self.intervals.get_mut(0).unwrap();
             first.from_id = range_to_insert.from_id;
             first.to_id = range_to_insert.to_id;
-            return;
+            self.values_count = range_to_insert.values_count();
+            self.after_mutation("enqueue_range");
+            return Ok(Some(self.values_count));
+        }
+
+        // The queue is not empty, so it can not miss all 2^128 values and zero means every value is present
+        let values_to_add = range_to_insert
+            .values_count()
+            .wrapping_sub(self.count_values_in_range(&range_to_insert));
+
+        if values_to_add == 0 {
+            return Ok(None);
         }
 
         let (from_index, to_index) = IndexRange::new(&self.intervals, &range_to_insert);
 
         match from_index {
             IndexRange::First => {
-                self.enqueue_as_first_as_between(0, to_index, range_to_insert);
+                self.enqueue_as_first_as_between(0, to_index, range_to_insert)?;
             }
             IndexRange::Exact(from_index) => {
-                self.enqueue_as_left_as_exact(from_index, to_index, range_to_insert);
+                self.enqueue_as_left_as_exact(from_index, to_index, range_to_insert)?;
             }
             IndexRange::Last => {
-                self.enqueue_as_first_as_last(to_index, range_to_insert);
+                self.enqueue_as_first_as_last(to_index, range_to_insert)?;
             }
             IndexRange::Between {
                 left_index: _,
                 right_index,
             } => {
-                self.enqueue_as_first_as_between(right_index, to_index, range_to_insert);
+                self.enqueue_as_first_as_between(right_index, to_index, range_to_insert)?;
             }
             IndexRange::JoinToIndexFrom(from_index) => {
-                self.enqueue_as_left_as_joint_to_index_from(from_index, to_index, range_to_insert)
+                self.enqueue_as_left_as_joint_to_index_from(from_index, to_index, range_to_insert)?;
             }
             IndexRange::JoinToIndexTo(from_index) => {
-                self.enqueue_as_left_as_join_to_index_to(from_index, to_index, range_to_insert)
+                self.enqueue_as_left_as_join_to_index_to(from_index, to_index, range_to_insert)?;
             }
             IndexRange::MergeIntervals(index) => {
-                self.enqueue_as_first_merge_intervals(index, to_index, range_to_insert);
+                self.enqueue_as_first_merge_intervals(index, to_index, range_to_insert)?;
             }
         }
+
+        self.values_count = self.values_count.wrapping_add(values_to_add);
+        self.after_mutation("enqueue_range");
+
+        Ok(Some(values_to_add))
     }
 
     fn enqueue_as_first_merge_intervals(
@@ -49,22 +85,19 @@ impl<T: QueueValue> QueueWithIntervals<T> {
         from_index: usize,
         to_index: IndexRange,
         range_to_insert: QueueIndexRange<T>,
-    ) {
+    ) -> Result<(), QueueWithIntervalsError> {
         match to_index {
             IndexRange::Exact(index) => {
-                self.do_update(from_index, index, None, None);
+                self.do_update(from_index, index, None, None)?;
             }
             IndexRange::JoinToIndexFrom(index) => {
-                self.do_update(from_index, index, None, None);
+                self.do_update(from_index, index, None, None)?;
             }
             IndexRange::JoinToIndexTo(index) => {
-                self.do_update(from_index, index, None, Some(range_to_insert.to_id));
+                self.do_update(from_index, index, None, Some(range_to_insert.to_id))?;
             }
             IndexRange::First => {
-                panic!(
-                    "Not possible be at first interval. Intervals: {:?}. range_to_insert {:?}",
-                    self.intervals, range_to_insert
-                );
+                return Err(QueueWithIntervalsError::InconsistentIntervals);
             }
             IndexRange::Last => {
                 self.do_update(
@@ -72,79 +105,44 @@ impl<T: QueueValue> QueueWithIntervals<T> {
                     self.intervals.len() - 1,
                     None,
                     Some(range_to_insert.to_id),
-                );
+                )?;
             }
             IndexRange::Between {
                 left_index,
                 right_index: _,
             } => {
-                self.do_update(from_index, left_index, None, range_to_insert.to_id.into());
+                self.do_update(from_index, left_index, None, range_to_insert.to_id.into())?;
             }
             IndexRange::MergeIntervals(index) => {
-                self.do_update(from_index, index + 1, None, None);
+                self.do_update(from_index, index + 1, None, None)?;
             }
         }
+
+        Ok(())
     }
 
     fn enqueue_as_first_as_last(
         &mut self,
         to_index: IndexRange,
         range_to_insert: QueueIndexRange<T>,
-    ) {
-        match to_index {
-            IndexRange::Exact(_) => {
-                panic!(
-                    "Index can not be between Last and other element. Intervals: {:?}. RangeToInsert: {:?}",
-                    self.intervals, range_to_insert
-                );
-            }
-            IndexRange::First => {
-                panic!(
-                    "Index can not be between Last and First elements. Intervals: {:?}. RangeToInsert: {:?}",
-                    self.intervals, range_to_insert
-                );
-            }
-            IndexRange::Last => {
-                let last = self.intervals.last_mut().unwrap();
+    ) -> Result<(), QueueWithIntervalsError> {
+        // Range which starts after the last interval can only end after it as well
+        let IndexRange::Last = to_index else {
+            return Err(QueueWithIntervalsError::InconsistentIntervals);
+        };
 
-                if let Some(last_to_plus_one) = last.to_id.checked_add_one() {
-                    if last_to_plus_one == range_to_insert.from_id {
-                        last.to_id = range_to_insert.to_id;
-                        return;
-                    }
-                }
+        let last = self.intervals.last_mut().unwrap();
 
-                self.intervals.push(range_to_insert);
-                return;
-            }
-            IndexRange::Between {
-                left_index: _,
-                right_index: _,
-            } => {
-                panic!(
-                    "Index can not be between Last and Between elements. Intervals: {:?}. RangeToInsert: {:?}",
-                    self.intervals, range_to_insert
-                );
-            }
-            IndexRange::JoinToIndexFrom(index) => {
-                panic!(
-                    "Index can not be between Last and JointToIndexFrom({}). Intervals: {:?}. range_to_insert: {:?}",
-                    index, self.intervals, range_to_insert
-                );
-            }
-            IndexRange::JoinToIndexTo(index) => {
-                panic!(
-                    "Index can not be between Last and join_index_to {} element. Intervals: {:?}. range_to_insert: {:?}",
-                    index, self.intervals, range_to_insert
-                )
-            }
-            IndexRange::MergeIntervals(index) => {
-                panic!(
-                    "Index can not be between Last and Merge Intervals [{}] elements. Intervals: {:?}. range_to_insert: {:?}",
-                    index, self.intervals, range_to_insert
-                );
-            }
+        if let Some(last_to_plus_one) = last.to_id.checked_add_one()
+            && last_to_plus_one == range_to_insert.from_id
+        {
+            last.to_id = range_to_insert.to_id;
+            return Ok(());
         }
+
+        self.intervals.push(range_to_insert);
+
+        Ok(())
     }
 
     fn enqueue_as_first_as_between(
@@ -152,17 +150,14 @@ impl<T: QueueValue> QueueWithIntervals<T> {
         left_index_to: usize,
         to_index: IndexRange,
         range_to_insert: QueueIndexRange<T>,
-    ) {
+    ) -> Result<(), QueueWithIntervalsError> {
         match to_index {
             IndexRange::Exact(to_index) => {
-                self.do_update(left_index_to, to_index, Some(range_to_insert.from_id), None);
+                self.do_update(left_index_to, to_index, Some(range_to_insert.from_id), None)?;
             }
             IndexRange::First => {
                 if left_index_to > 0 {
-                    panic!(
-                        "Somehow right_index={} and left index=0. Intervals: {:?}. range_to_insert: {:?}",
-                        left_index_to, self.intervals, range_to_insert
-                    )
+                    return Err(QueueWithIntervalsError::InconsistentIntervals);
                 }
 
                 self.intervals.insert(0, range_to_insert);
@@ -173,7 +168,7 @@ impl<T: QueueValue> QueueWithIntervals<T> {
                     self.intervals.len() - 1,
                     Some(range_to_insert.from_id),
                     Some(range_to_insert.to_id),
-                );
+                )?;
             }
             IndexRange::Between {
                 left_index: to_left_index,
@@ -181,7 +176,7 @@ impl<T: QueueValue> QueueWithIntervals<T> {
             } => {
                 if left_index_to == to_right_index {
                     self.intervals.insert(to_right_index, range_to_insert);
-                    return;
+                    return Ok(());
                 }
 
                 self.do_update(
@@ -189,10 +184,10 @@ impl<T: QueueValue> QueueWithIntervals<T> {
                     to_left_index,
                     Some(range_to_insert.from_id),
                     Some(range_to_insert.to_id),
-                );
+                )?;
             }
             IndexRange::JoinToIndexFrom(index) => {
-                self.do_update(left_index_to, index, Some(range_to_insert.from_id), None);
+                self.do_update(left_index_to, index, Some(range_to_insert.from_id), None)?;
             }
             IndexRange::JoinToIndexTo(index) => {
                 self.do_update(
@@ -200,7 +195,7 @@ impl<T: QueueValue> QueueWithIntervals<T> {
                     index,
                     Some(range_to_insert.from_id),
                     Some(range_to_insert.to_id),
-                );
+                )?;
             }
             IndexRange::MergeIntervals(index) => {
                 self.do_update(
@@ -208,9 +203,11 @@ impl<T: QueueValue> QueueWithIntervals<T> {
                     index + 1,
                     Some(range_to_insert.from_id),
                     None,
-                );
+                )?;
             }
         }
+
+        Ok(())
     }
 
     fn enqueue_as_left_as_joint_to_index_from(
@@ -218,13 +215,13 @@ impl<T: QueueValue> QueueWithIntervals<T> {
         from_index: usize,
         to_index: IndexRange,
         range_to_insert: QueueIndexRange<T>,
-    ) {
+    ) -> Result<(), QueueWithIntervalsError> {
         match to_index {
             IndexRange::Exact(to_index) => {
-                self.do_update(from_index, to_index, Some(range_to_insert.from_id), None);
+                self.do_update(from_index, to_index, Some(range_to_insert.from_id), None)?;
             }
             IndexRange::First => {
-                panic!("Position between some interval and first element is not possible");
+                return Err(QueueWithIntervalsError::InconsistentIntervals);
             }
             IndexRange::Last => {
                 self.do_update(
@@ -232,7 +229,7 @@ impl<T: QueueValue> QueueWithIntervals<T> {
                     self.intervals.len() - 1,
                     Some(range_to_insert.from_id),
                     Some(range_to_insert.to_id),
-                );
+                )?;
             }
             IndexRange::Between {
                 left_index,
@@ -243,11 +240,11 @@ impl<T: QueueValue> QueueWithIntervals<T> {
                     left_index,
                     Some(range_to_insert.from_id),
                     Some(range_to_insert.to_id),
-                );
+                )?;
                 //  self.insert_with_override_to_left(from_index, left_index, range_to_insert);
             }
             IndexRange::JoinToIndexFrom(to_index) => {
-                self.do_update(from_index, to_index, Some(range_to_insert.from_id), None);
+                self.do_update(from_index, to_index, Some(range_to_insert.from_id), None)?;
             }
             IndexRange::JoinToIndexTo(to_index) => {
                 self.do_update(
@@ -255,12 +252,14 @@ impl<T: QueueValue> QueueWithIntervals<T> {
                     to_index,
                     Some(range_to_insert.from_id),
                     Some(range_to_insert.to_id),
-                );
+                )?;
             }
             IndexRange::MergeIntervals(index) => {
-                self.do_update(from_index, index + 1, Some(range_to_insert.from_id), None);
+                self.do_update(from_index, index + 1, Some(range_to_insert.from_id), None)?;
             }
         }
+
+        Ok(())
     }
 
     fn enqueue_as_left_as_join_to_index_to(
@@ -268,13 +267,13 @@ impl<T: QueueValue> QueueWithIntervals<T> {
         from_index: usize,
         to_index: IndexRange,
         range_to_insert: QueueIndexRange<T>,
-    ) {
+    ) -> Result<(), QueueWithIntervalsError> {
         match to_index {
             IndexRange::Exact(to_index) => {
-                self.do_update(from_index, to_index, None, None);
+                self.do_update(from_index, to_index, None, None)?;
             }
             IndexRange::First => {
-                panic!("Position between some interval and first element is not possible");
+                return Err(QueueWithIntervalsError::InconsistentIntervals);
             }
             IndexRange::Last => {
                 self.do_update(
@@ -282,24 +281,26 @@ impl<T: QueueValue> QueueWithIntervals<T> {
                     self.intervals.len() - 1,
                     None,
                     Some(range_to_insert.to_id),
-                );
+                )?;
             }
             IndexRange::Between {
                 left_index,
                 right_index: _,
             } => {
-                self.do_update(from_index, left_index, None, range_to_insert.to_id.into());
+                self.do_update(from_index, left_index, None, range_to_insert.to_id.into())?;
             }
             IndexRange::JoinToIndexFrom(to_index) => {
-                self.do_update(from_index, to_index, None, None);
+                self.do_update(from_index, to_index, None, None)?;
             }
             IndexRange::JoinToIndexTo(index) => {
-                self.do_update(from_index, index, None, Some(range_to_insert.to_id));
+                self.do_update(from_index, index, None, Some(range_to_insert.to_id))?;
             }
             IndexRange::MergeIntervals(index) => {
-                self.do_update(from_index, index + 1, None, None);
+                self.do_update(from_index, index + 1, None, None)?;
             }
         }
+
+        Ok(())
     }
 
     fn enqueue_as_left_as_exact(
@@ -307,13 +308,13 @@ impl<T: QueueValue> QueueWithIntervals<T> {
         from_index: usize,
         to_index: IndexRange,
         range_to_insert: QueueIndexRange<T>,
-    ) {
+    ) -> Result<(), QueueWithIntervalsError> {
         match to_index {
             IndexRange::Exact(to_index) => {
-                self.do_update(from_index, to_index, None, None);
+                self.do_update(from_index, to_index, None, None)?;
             }
             IndexRange::First => {
-                panic!("Position between some interval and first element is not possible");
+                return Err(QueueWithIntervalsError::InconsistentIntervals);
             }
             IndexRange::Last => {
                 self.do_update(
@@ -321,29 +322,26 @@ impl<T: QueueValue> QueueWithIntervals<T> {
                     self.intervals.len() - 1,
                     None,
                     Some(range_to_insert.to_id),
-                );
+                )?;
             }
             IndexRange::Between {
                 left_index,
                 right_index: _,
             } => {
-                self.do_update(from_index, left_index, None, range_to_insert.to_id.into());
+                self.do_update(from_index, left_index, None, range_to_insert.to_id.into())?;
             }
             IndexRange::JoinToIndexFrom(index) => {
-                self.do_update(from_index, index, Some(range_to_insert.from_id), None);
+                self.do_update(from_index, index, None, None)?;
             }
             IndexRange::JoinToIndexTo(to_index) => {
-                self.do_update(
-                    from_index,
-                    to_index,
-                    Some(range_to_insert.from_id),
-                    Some(range_to_insert.to_id),
-                );
+                self.do_update(from_index, to_index, None, Some(range_to_insert.to_id))?;
             }
             IndexRange::MergeIntervals(index) => {
-                self.do_update(from_index, index + 1, Some(range_to_insert.from_id), None);
+                self.do_update(from_index, index + 1, None, None)?;
             }
         }
+
+        Ok(())
     }
 
     fn do_update(
@@ -352,27 +350,30 @@ impl<T: QueueValue> QueueWithIntervals<T> {
         to_index: usize,
         override_from_id: Option<T>,
         override_to_id: Option<T>,
-    ) {
-        let to_id = if let Some(override_to_id) = override_to_id {
-            override_to_id
-        } else {
-            self.intervals.get(to_index).unwrap().to_id
+    ) -> Result<(), QueueWithIntervalsError> {
+        let to_id = match override_to_id {
+            Some(override_to_id) => override_to_id,
+            None => match self.intervals.get(to_index) {
+                Some(interval) => interval.to_id,
+                None => return Err(QueueWithIntervalsError::InconsistentIntervals),
+            },
         };
 
-        for _ in from_index..to_index {
-            self.intervals.remove(from_index + 1);
-        }
-        if self.intervals.len() == 0 {
-            panic!("Somehow intervals got empty");
+        if from_index < to_index {
+            self.intervals.drain(from_index + 1..=to_index);
         }
 
-        let first = self.intervals.get_mut(from_index).unwrap();
+        let Some(first) = self.intervals.get_mut(from_index) else {
+            return Err(QueueWithIntervalsError::InconsistentIntervals);
+        };
 
         if let Some(from_id) = override_from_id {
             first.from_id = from_id;
         }
 
         first.to_id = to_id;
+
+        Ok(())
     }
 }
 
@@ -396,8 +397,8 @@ mod tests_left_is_exact {
 
         assert_eq!(3, queue.intervals.len());
 
-        assert_eq!(10, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(40, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(10, queue.intervals.first().unwrap().from_id);
+        assert_eq!(40, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(50, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(60, queue.intervals.get(1).unwrap().to_id);
@@ -422,8 +423,8 @@ mod tests_left_is_exact {
 
         assert_eq!(4, queue.intervals.len());
 
-        assert_eq!(10, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(21, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(10, queue.intervals.first().unwrap().from_id);
+        assert_eq!(21, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(30, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(40, queue.intervals.get(1).unwrap().to_id);
@@ -451,8 +452,8 @@ mod tests_left_is_exact {
 
         assert_eq!(3, queue.intervals.len());
 
-        assert_eq!(10, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(41, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(10, queue.intervals.first().unwrap().from_id);
+        assert_eq!(41, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(50, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(60, queue.intervals.get(1).unwrap().to_id);
@@ -477,8 +478,8 @@ mod tests_left_is_exact {
 
         assert_eq!(1, queue.intervals.len());
 
-        assert_eq!(10, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(81, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(10, queue.intervals.first().unwrap().from_id);
+        assert_eq!(81, queue.intervals.first().unwrap().to_id);
     }
 
     #[test]
@@ -497,8 +498,8 @@ mod tests_left_is_exact {
 
         assert_eq!(3, queue.intervals.len());
 
-        assert_eq!(10, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(30, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(10, queue.intervals.first().unwrap().from_id);
+        assert_eq!(30, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(40, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(50, queue.intervals.get(1).unwrap().to_id);
@@ -523,8 +524,8 @@ mod tests_left_is_exact {
 
         assert_eq!(2, queue.intervals.len());
 
-        assert_eq!(10, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(50, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(10, queue.intervals.first().unwrap().from_id);
+        assert_eq!(50, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(60, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(70, queue.intervals.get(1).unwrap().to_id);
@@ -546,8 +547,8 @@ mod tests_left_is_exact {
 
         assert_eq!(4, queue.intervals.len());
 
-        assert_eq!(10, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(20, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(10, queue.intervals.first().unwrap().from_id);
+        assert_eq!(20, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(30, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(45, queue.intervals.get(1).unwrap().to_id);
@@ -575,8 +576,8 @@ mod tests_left_is_exact {
 
         assert_eq!(2, queue.intervals.len());
 
-        assert_eq!(10, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(20, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(10, queue.intervals.first().unwrap().from_id);
+        assert_eq!(20, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(30, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(85, queue.intervals.get(1).unwrap().to_id);
@@ -598,8 +599,8 @@ mod tests_left_is_exact {
 
         assert_eq!(3, queue.intervals.len());
 
-        assert_eq!(10, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(20, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(10, queue.intervals.first().unwrap().from_id);
+        assert_eq!(20, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(30, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(65, queue.intervals.get(1).unwrap().to_id);
@@ -629,8 +630,8 @@ mod tests_left_as_join_to_index_to {
 
         assert_eq!(3, queue.intervals.len());
 
-        assert_eq!(10, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(40, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(10, queue.intervals.first().unwrap().from_id);
+        assert_eq!(40, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(50, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(60, queue.intervals.get(1).unwrap().to_id);
@@ -655,8 +656,8 @@ mod tests_left_as_join_to_index_to {
 
         assert_eq!(3, queue.intervals.len());
 
-        assert_eq!(10, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(41, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(10, queue.intervals.first().unwrap().from_id);
+        assert_eq!(41, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(50, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(60, queue.intervals.get(1).unwrap().to_id);
@@ -681,8 +682,8 @@ mod tests_left_as_join_to_index_to {
 
         assert_eq!(4, queue.intervals.len());
 
-        assert_eq!(10, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(25, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(10, queue.intervals.first().unwrap().from_id);
+        assert_eq!(25, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(30, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(40, queue.intervals.get(1).unwrap().to_id);
@@ -710,8 +711,8 @@ mod tests_left_as_join_to_index_to {
 
         assert_eq!(3, queue.intervals.len());
 
-        assert_eq!(10, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(40, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(10, queue.intervals.first().unwrap().from_id);
+        assert_eq!(40, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(50, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(60, queue.intervals.get(1).unwrap().to_id);
@@ -736,8 +737,8 @@ mod tests_left_as_join_to_index_to {
 
         assert_eq!(2, queue.intervals.len());
 
-        assert_eq!(10, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(60, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(10, queue.intervals.first().unwrap().from_id);
+        assert_eq!(60, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(70, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(80, queue.intervals.get(1).unwrap().to_id);
@@ -759,8 +760,8 @@ mod tests_left_as_join_to_index_to {
 
         assert_eq!(1, queue.intervals.len());
 
-        assert_eq!(10, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(85, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(10, queue.intervals.first().unwrap().from_id);
+        assert_eq!(85, queue.intervals.first().unwrap().to_id);
     }
 
     #[test]
@@ -779,8 +780,8 @@ mod tests_left_as_join_to_index_to {
 
         assert_eq!(2, queue.intervals.len());
 
-        assert_eq!(10, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(20, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(10, queue.intervals.first().unwrap().from_id);
+        assert_eq!(20, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(30, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(85, queue.intervals.get(1).unwrap().to_id);
@@ -801,8 +802,8 @@ mod tests_left_as_join_to_index_to {
 
         assert_eq!(3, queue.intervals.len());
 
-        assert_eq!(10, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(20, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(10, queue.intervals.first().unwrap().from_id);
+        assert_eq!(20, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(30, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(60, queue.intervals.get(1).unwrap().to_id);
@@ -827,8 +828,8 @@ mod tests_left_as_join_to_index_to {
 
         assert_eq!(3, queue.intervals.len());
 
-        assert_eq!(10, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(41, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(10, queue.intervals.first().unwrap().from_id);
+        assert_eq!(41, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(50, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(60, queue.intervals.get(1).unwrap().to_id);
@@ -853,8 +854,8 @@ mod tests_left_as_join_to_index_to {
 
         assert_eq!(2, queue.intervals.len());
 
-        assert_eq!(10, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(60, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(10, queue.intervals.first().unwrap().from_id);
+        assert_eq!(60, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(70, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(80, queue.intervals.get(1).unwrap().to_id);
@@ -882,8 +883,8 @@ mod tests_left_as_join_to_index_from {
 
         assert_eq!(3, queue.intervals.len());
 
-        assert_eq!(9, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(40, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(9, queue.intervals.first().unwrap().from_id);
+        assert_eq!(40, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(50, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(60, queue.intervals.get(1).unwrap().to_id);
@@ -908,8 +909,8 @@ mod tests_left_as_join_to_index_from {
 
         assert_eq!(4, queue.intervals.len());
 
-        assert_eq!(9, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(21, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(9, queue.intervals.first().unwrap().from_id);
+        assert_eq!(21, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(30, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(40, queue.intervals.get(1).unwrap().to_id);
@@ -937,8 +938,8 @@ mod tests_left_as_join_to_index_from {
 
         assert_eq!(4, queue.intervals.len());
 
-        assert_eq!(9, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(25, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(9, queue.intervals.first().unwrap().from_id);
+        assert_eq!(25, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(30, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(40, queue.intervals.get(1).unwrap().to_id);
@@ -966,8 +967,8 @@ mod tests_left_as_join_to_index_from {
 
         assert_eq!(3, queue.intervals.len());
 
-        assert_eq!(9, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(40, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(9, queue.intervals.first().unwrap().from_id);
+        assert_eq!(40, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(50, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(60, queue.intervals.get(1).unwrap().to_id);
@@ -992,8 +993,8 @@ mod tests_left_as_join_to_index_from {
 
         assert_eq!(1, queue.intervals.len());
 
-        assert_eq!(9, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(85, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(9, queue.intervals.first().unwrap().from_id);
+        assert_eq!(85, queue.intervals.first().unwrap().to_id);
     }
 
     #[test]
@@ -1011,8 +1012,8 @@ mod tests_left_as_join_to_index_from {
 
         assert_eq!(3, queue.intervals.len());
 
-        assert_eq!(9, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(40, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(9, queue.intervals.first().unwrap().from_id);
+        assert_eq!(40, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(50, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(60, queue.intervals.get(1).unwrap().to_id);
@@ -1036,8 +1037,8 @@ mod tests_left_as_join_to_index_from {
 
         assert_eq!(2, queue.intervals.len());
 
-        assert_eq!(9, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(60, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(9, queue.intervals.first().unwrap().from_id);
+        assert_eq!(60, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(70, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(80, queue.intervals.get(1).unwrap().to_id);
@@ -1064,8 +1065,8 @@ mod tests_left_is_between {
 
         assert_eq!(4, queue.intervals.len());
 
-        assert_eq!(10, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(20, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(10, queue.intervals.first().unwrap().from_id);
+        assert_eq!(20, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(25, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(40, queue.intervals.get(1).unwrap().to_id);
@@ -1093,8 +1094,8 @@ mod tests_left_is_between {
 
         assert_eq!(4, queue.intervals.len());
 
-        assert_eq!(10, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(20, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(10, queue.intervals.first().unwrap().from_id);
+        assert_eq!(20, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(25, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(40, queue.intervals.get(1).unwrap().to_id);
@@ -1122,8 +1123,8 @@ mod tests_left_is_between {
 
         assert_eq!(4, queue.intervals.len());
 
-        assert_eq!(10, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(20, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(10, queue.intervals.first().unwrap().from_id);
+        assert_eq!(20, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(25, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(40, queue.intervals.get(1).unwrap().to_id);
@@ -1151,8 +1152,8 @@ mod tests_left_is_between {
 
         assert_eq!(3, queue.intervals.len());
 
-        assert_eq!(10, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(20, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(10, queue.intervals.first().unwrap().from_id);
+        assert_eq!(20, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(25, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(60, queue.intervals.get(1).unwrap().to_id);
@@ -1177,8 +1178,8 @@ mod tests_left_is_between {
 
         assert_eq!(4, queue.intervals.len());
 
-        assert_eq!(10, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(20, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(10, queue.intervals.first().unwrap().from_id);
+        assert_eq!(20, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(25, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(45, queue.intervals.get(1).unwrap().to_id);
@@ -1206,8 +1207,8 @@ mod tests_left_is_between {
 
         assert_eq!(3, queue.intervals.len());
 
-        assert_eq!(10, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(20, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(10, queue.intervals.first().unwrap().from_id);
+        assert_eq!(20, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(30, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(40, queue.intervals.get(1).unwrap().to_id);
@@ -1232,8 +1233,8 @@ mod tests_left_is_between {
 
         assert_eq!(4, queue.intervals.len());
 
-        assert_eq!(10, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(20, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(10, queue.intervals.first().unwrap().from_id);
+        assert_eq!(20, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(30, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(40, queue.intervals.get(1).unwrap().to_id);
@@ -1261,8 +1262,8 @@ mod tests_left_is_between {
 
         assert_eq!(3, queue.intervals.len());
 
-        assert_eq!(10, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(20, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(10, queue.intervals.first().unwrap().from_id);
+        assert_eq!(20, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(25, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(65, queue.intervals.get(1).unwrap().to_id);
@@ -1287,8 +1288,8 @@ mod tests_left_is_between {
 
         assert_eq!(4, queue.intervals.len());
 
-        assert_eq!(10, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(20, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(10, queue.intervals.first().unwrap().from_id);
+        assert_eq!(20, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(25, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(45, queue.intervals.get(1).unwrap().to_id);
@@ -1317,8 +1318,8 @@ mod tests_left_is_between {
 
         assert_eq!(3, queue.intervals.len());
 
-        assert_eq!(10, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(20, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(10, queue.intervals.first().unwrap().from_id);
+        assert_eq!(20, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(30, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(80, queue.intervals.get(1).unwrap().to_id);
@@ -1349,8 +1350,8 @@ mod tests_left_is_first {
 
         assert_eq!(2, queue.intervals.len());
 
-        assert_eq!(5, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(70, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(5, queue.intervals.first().unwrap().from_id);
+        assert_eq!(70, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(80, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(90, queue.intervals.get(1).unwrap().to_id);
@@ -1372,8 +1373,8 @@ mod tests_left_is_first {
 
         assert_eq!(3, queue.intervals.len());
 
-        assert_eq!(5, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(40, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(5, queue.intervals.first().unwrap().from_id);
+        assert_eq!(40, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(50, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(60, queue.intervals.get(1).unwrap().to_id);
@@ -1398,8 +1399,8 @@ mod tests_left_is_first {
 
         assert_eq!(3, queue.intervals.len());
 
-        assert_eq!(5, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(45, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(5, queue.intervals.first().unwrap().from_id);
+        assert_eq!(45, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(50, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(60, queue.intervals.get(1).unwrap().to_id);
@@ -1424,8 +1425,8 @@ mod tests_left_is_first {
 
         assert_eq!(4, queue.intervals.len());
 
-        assert_eq!(5, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(25, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(5, queue.intervals.first().unwrap().from_id);
+        assert_eq!(25, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(30, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(40, queue.intervals.get(1).unwrap().to_id);
@@ -1453,8 +1454,8 @@ mod tests_left_is_first {
 
         assert_eq!(4, queue.intervals.len());
 
-        assert_eq!(8, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(21, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(8, queue.intervals.first().unwrap().from_id);
+        assert_eq!(21, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(30, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(40, queue.intervals.get(1).unwrap().to_id);
@@ -1482,8 +1483,8 @@ mod tests_left_is_first {
 
         assert_eq!(1, queue.intervals.len());
 
-        assert_eq!(5, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(85, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(5, queue.intervals.first().unwrap().from_id);
+        assert_eq!(85, queue.intervals.first().unwrap().to_id);
     }
 
     #[test]
@@ -1493,8 +1494,8 @@ mod tests_left_is_first {
         queue.enqueue_range(QueueIndexRange::restore(20, 25));
         queue.enqueue_range(QueueIndexRange::restore(10, 15));
 
-        assert_eq!(10, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(15, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(10, queue.intervals.first().unwrap().from_id);
+        assert_eq!(15, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(20, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(25, queue.intervals.get(1).unwrap().to_id);
@@ -1506,8 +1507,8 @@ mod tests_left_is_first {
 
         assert_eq!(2, queue.intervals.len());
 
-        assert_eq!(5, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(15, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(5, queue.intervals.first().unwrap().from_id);
+        assert_eq!(15, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(20, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(25, queue.intervals.get(1).unwrap().to_id);
@@ -1526,8 +1527,8 @@ mod tests_left_is_first {
 
         assert_eq!(1, queue.intervals.len());
 
-        assert_eq!(5, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(20, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(5, queue.intervals.first().unwrap().from_id);
+        assert_eq!(20, queue.intervals.first().unwrap().to_id);
     }
 }
 
@@ -1551,8 +1552,8 @@ mod tests_left_is_merge_two_intervals {
 
         assert_eq!(3, queue.intervals.len());
 
-        assert_eq!(10, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(40, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(10, queue.intervals.first().unwrap().from_id);
+        assert_eq!(40, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(50, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(60, queue.intervals.get(1).unwrap().to_id);
@@ -1577,8 +1578,8 @@ mod tests_left_is_merge_two_intervals {
 
         assert_eq!(2, queue.intervals.len());
 
-        assert_eq!(10, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(60, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(10, queue.intervals.first().unwrap().from_id);
+        assert_eq!(60, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(70, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(80, queue.intervals.get(1).unwrap().to_id);
@@ -1600,8 +1601,8 @@ mod tests_left_is_merge_two_intervals {
 
         assert_eq!(3, queue.intervals.len());
 
-        assert_eq!(10, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(45, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(10, queue.intervals.first().unwrap().from_id);
+        assert_eq!(45, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(50, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(60, queue.intervals.get(1).unwrap().to_id);
@@ -1626,8 +1627,8 @@ mod tests_left_is_merge_two_intervals {
 
         assert_eq!(2, queue.intervals.len());
 
-        assert_eq!(10, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(65, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(10, queue.intervals.first().unwrap().from_id);
+        assert_eq!(65, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(70, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(80, queue.intervals.get(1).unwrap().to_id);
@@ -1649,8 +1650,8 @@ mod tests_left_is_merge_two_intervals {
 
         assert_eq!(2, queue.intervals.len());
 
-        assert_eq!(10, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(60, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(10, queue.intervals.first().unwrap().from_id);
+        assert_eq!(60, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(70, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(80, queue.intervals.get(1).unwrap().to_id);
@@ -1673,8 +1674,8 @@ mod tests_left_is_merge_two_intervals {
 
         assert_eq!(2, queue.intervals.len());
 
-        assert_eq!(10, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(70, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(10, queue.intervals.first().unwrap().from_id);
+        assert_eq!(70, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(80, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(90, queue.intervals.get(1).unwrap().to_id);
@@ -1697,8 +1698,8 @@ mod tests_left_is_merge_two_intervals {
 
         assert_eq!(1, queue.intervals.len());
 
-        assert_eq!(10, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(95, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(10, queue.intervals.first().unwrap().from_id);
+        assert_eq!(95, queue.intervals.first().unwrap().to_id);
     }
 
     #[test]
@@ -1717,8 +1718,8 @@ mod tests_left_is_merge_two_intervals {
 
         assert_eq!(3, queue.intervals.len());
 
-        assert_eq!(10, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(31, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(10, queue.intervals.first().unwrap().from_id);
+        assert_eq!(31, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(40, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(50, queue.intervals.get(1).unwrap().to_id);
@@ -1743,8 +1744,8 @@ mod tests_left_is_merge_two_intervals {
 
         assert_eq!(2, queue.intervals.len());
 
-        assert_eq!(10, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(51, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(10, queue.intervals.first().unwrap().from_id);
+        assert_eq!(51, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(60, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(70, queue.intervals.get(1).unwrap().to_id);
@@ -1766,10 +1767,48 @@ mod tests_left_is_merge_two_intervals {
 
         assert_eq!(2, queue.intervals.len());
 
-        assert_eq!(10, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(50, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(10, queue.intervals.first().unwrap().from_id);
+        assert_eq!(50, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(60, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(70, queue.intervals.get(1).unwrap().to_id);
     }
+
+    #[test]
+    fn test_single_value_joining_previous_interval() {
+        //Preparing data
+        let mut queue = QueueWithIntervals::new();
+        queue.enqueue_range(QueueIndexRange::restore(10, 20));
+        queue.enqueue_range(QueueIndexRange::restore(30, 40));
+
+        // Executing data
+        queue.enqueue_range(QueueIndexRange::restore(21, 21));
+
+        assert_eq!(2, queue.intervals.len());
+
+        assert_eq!(10, queue.intervals.first().unwrap().from_id);
+        assert_eq!(21, queue.intervals.first().unwrap().to_id);
+
+        assert_eq!(30, queue.intervals.get(1).unwrap().from_id);
+        assert_eq!(40, queue.intervals.get(1).unwrap().to_id);
+    }
+
+    #[test]
+    fn test_left_is_exact_and_join_to_index_to_keeps_from_id() {
+        //Preparing data
+        let mut queue = QueueWithIntervals::new();
+        queue.enqueue_range(QueueIndexRange::restore(10, 20));
+        queue.enqueue_range(QueueIndexRange::restore(30, 40));
+
+        // Executing data
+        queue.enqueue_range(QueueIndexRange::restore(15, 21));
+
+        assert_eq!(2, queue.intervals.len());
+
+        assert_eq!(10, queue.intervals.first().unwrap().from_id);
+        assert_eq!(21, queue.intervals.first().unwrap().to_id);
+
+        assert_eq!(30, queue.intervals.get(1).unwrap().from_id);
+        assert_eq!(40, queue.intervals.get(1).unwrap().to_id);
+    }
 }
@@ -0,0 +1,111 @@
+use super::{QueueIndexRange, QueueWithIntervals};
+use crate::QueueValue;
+
+// Input is collected, sorted and coalesced in one pass instead of looking up the insert position
+// for every single item
+
+impl<T: QueueValue> FromIterator<T> for QueueWithIntervals<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::restore_normalized(
+            iter.into_iter()
+                .map(|value| QueueIndexRange::restore(value, value))
+                .collect(),
+        )
+    }
+}
+
+impl<T: QueueValue> FromIterator<QueueIndexRange<T>> for QueueWithIntervals<T> {
+    fn from_iter<I: IntoIterator<Item = QueueIndexRange<T>>>(iter: I) -> Self {
+        Self::restore_normalized(iter.into_iter().collect())
+    }
+}
+
+impl<T: QueueValue> Extend<T> for QueueWithIntervals<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let other: Self = iter.into_iter().collect();
+        self.extend_with_queue(other);
+    }
+}
+
+impl<T: QueueValue> Extend<QueueIndexRange<T>> for QueueWithIntervals<T> {
+    fn extend<I: IntoIterator<Item = QueueIndexRange<T>>>(&mut self, iter: I) {
+        let other: Self = iter.into_iter().collect();
+        self.extend_with_queue(other);
+    }
+}
+
+impl<T: QueueValue> QueueWithIntervals<T> {
+    fn extend_with_queue(&mut self, other: Self) {
+        if other.is_empty() {
+            return;
+        }
+
+        if self.is_empty() {
+            self.set_sorted_intervals(other.intervals);
+            return;
+        }
+
+        // A single interval is cheaper to enqueue than to merge both interval lists
+        if other.intervals.len() == 1 {
+            self.enqueue_range(other.intervals[0].clone());
+            return;
+        }
+
+        self.union_with(&other);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{QueueIndexRange, QueueWithIntervals};
+
+    #[test]
+    fn test_collect_values() {
+        let queue: QueueWithIntervals<i64> = [5, 3, 4, 10, 1, 3, 11, -2].into_iter().collect();
+        assert_eq!(queue.to_string(), "-2,1,3-5,10-11");
+        assert_eq!(queue.len(), 7);
+
+        let queue: QueueWithIntervals<u8> = std::iter::empty::<u8>().collect();
+        assert!(queue.is_empty());
+
+        let queue: QueueWithIntervals<u8> = (0..=u8::MAX).rev().collect();
+        assert_eq!(queue.to_string(), "0-255");
+    }
+
+    #[test]
+    fn test_collect_ranges() {
+        let queue: QueueWithIntervals<i64> = vec![
+            QueueIndexRange::restore(20, 30),
+            QueueIndexRange::new_empty(100),
+            QueueIndexRange::restore(1, 5),
+            QueueIndexRange::restore(6, 8),
+            QueueIndexRange::restore(25, 40),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(queue.to_string(), "1-8,20-40");
+        assert_eq!(queue.len(), 29);
+    }
+
+    #[test]
+    fn test_extend() {
+        let mut queue = QueueWithIntervals::new();
+        queue.extend([3, 1, 2]);
+        assert_eq!(queue.to_string(), "1-3");
+
+        queue.extend([7]);
+        queue.extend(std::iter::empty::<i64>());
+        assert_eq!(queue.to_string(), "1-3,7");
+
+        queue.extend([
+            QueueIndexRange::restore(4, 5),
+            QueueIndexRange::restore(10, 12),
+        ]);
+        assert_eq!(queue.to_string(), "1-5,7,10-12");
+
+        queue.extend([QueueIndexRange::restore(6, 9)]);
+        assert_eq!(queue.to_string(), "1-12");
+        assert_eq!(queue.len(), 12);
+    }
+}
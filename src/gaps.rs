@@ -0,0 +1,333 @@
+use std::iter::FusedIterator;
+
+use super::{QueueIndexRange, QueueWithIntervals, queue_with_intervals::saturate_count};
+use crate::QueueValue;
+
+pub struct QueueGapsIterator<'s, T: QueueValue> {
+    intervals: std::slice::Iter<'s, QueueIndexRange<T>>,
+    // Start of the next possible gap. None when there are no more gaps
+    next_from_id: Option<T>,
+    to_id: T,
+}
+
+impl<'s, T: QueueValue> QueueGapsIterator<'s, T> {
+    pub(crate) fn new(intervals: &'s [QueueIndexRange<T>], within: &QueueIndexRange<T>) -> Self {
+        Self {
+            intervals: intervals.iter(),
+            next_from_id: if within.is_empty() {
+                None
+            } else {
+                Some(within.from_id)
+            },
+            to_id: within.to_id,
+        }
+    }
+}
+
+impl<T: QueueValue> Iterator for QueueGapsIterator<'_, T> {
+    type Item = QueueIndexRange<T>;
+
+    fn next(&mut self) -> Option<QueueIndexRange<T>> {
+        loop {
+            let from_id = self.next_from_id?;
+
+            let Some(interval) = self.intervals.next() else {
+                self.next_from_id = None;
+                return Some(QueueIndexRange {
+                    from_id,
+                    to_id: self.to_id,
+                });
+            };
+
+            if interval.is_empty() || interval.to_id < from_id {
+                continue;
+            }
+
+            if interval.from_id > self.to_id {
+                self.next_from_id = None;
+                return Some(QueueIndexRange {
+                    from_id,
+                    to_id: self.to_id,
+                });
+            }
+
+            self.next_from_id = match interval.to_id.checked_add_one() {
+                Some(next_from_id) if next_from_id <= self.to_id => Some(next_from_id),
+                _ => None,
+            };
+
+            if interval.from_id > from_id {
+                return Some(QueueIndexRange {
+                    from_id,
+                    to_id: interval.from_id - T::one(),
+                });
+            }
+        }
+    }
+}
+
+impl<T: QueueValue> FusedIterator for QueueGapsIterator<'_, T> {}
+
+impl<T: QueueValue> QueueWithIntervals<T> {
+    /// Iterates sub-ranges of within which are missing in the queue
+    pub fn gaps(&self, within: QueueIndexRange<T>) -> QueueGapsIterator<'_, T> {
+        let intervals = self.get_non_empty_intervals();
+        let start = intervals.partition_point(|itm| itm.to_id < within.from_id);
+
+        QueueGapsIterator::new(&intervals[start..], &within)
+    }
+
+    /// Returns the first missing sub-range above the value which has queued values after it
+    pub fn first_gap_after(&self, value: T) -> Option<QueueIndexRange<T>> {
+        let max_id = self.get_max_id()?;
+        let from_id = value.checked_add_one()?;
+
+        self.gaps(QueueIndexRange::restore(from_id, max_id)).next()
+    }
+
+    /// Returns the amount of values of within which are missing in the queue, saturating at usize::MAX
+    pub fn missing_count(&self, within: QueueIndexRange<T>) -> usize {
+        let present = self.count_values_in_range(&within);
+
+        let missing = within.values_count().wrapping_sub(present);
+
+        // A zero amount misses something only if nothing of within is present
+        saturate_count(missing, present == 0 && !within.is_empty())
+    }
+
+    /// Returns values of the universe which are not in the queue.
+    /// Use QueueIndexRange::new_full() as the universe to complement within all values of the type
+    pub fn complement(&self, universe: QueueIndexRange<T>) -> Self {
+        Self::from_sorted_intervals(self.gaps(universe).collect())
+    }
+
+    /// Flips membership of every value of the universe. Values outside of the universe stay untouched
+    pub fn invert_within(&mut self, universe: QueueIndexRange<T>) {
+        if universe.is_empty() {
+            return;
+        }
+
+        self.symmetric_difference_with(&Self::from_sorted_intervals(vec![universe]));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{QueueIndexRange, QueueWithIntervals};
+
+    fn gaps(queue: &QueueWithIntervals<i64>, from_id: i64, to_id: i64) -> Vec<(i64, i64)> {
+        queue
+            .gaps(QueueIndexRange::restore(from_id, to_id))
+            .map(|itm| (itm.from_id, itm.to_id))
+            .collect()
+    }
+
+    #[test]
+    fn test_gaps() {
+        let mut queue = QueueWithIntervals::new();
+        queue.enqueue_range(QueueIndexRange::restore(100, 200));
+        queue.enqueue(250);
+        queue.enqueue(251);
+
+        assert_eq!(vec![(201, 249), (252, 300)], gaps(&queue, 100, 300));
+        assert_eq!(
+            vec![(90, 99), (201, 249), (252, 300)],
+            gaps(&queue, 90, 300)
+        );
+        assert_eq!(vec![(201, 249)], gaps(&queue, 150, 250));
+        assert_eq!(vec![(210, 220)], gaps(&queue, 210, 220));
+        assert_eq!(Vec::<(i64, i64)>::new(), gaps(&queue, 120, 180));
+        assert_eq!(vec![(0, 10)], gaps(&queue, 0, 10));
+        assert_eq!(vec![(300, 400)], gaps(&queue, 300, 400));
+    }
+
+    #[test]
+    fn test_gaps_on_empty_queue() {
+        let queue = QueueWithIntervals::new();
+        assert_eq!(vec![(5, 10)], gaps(&queue, 5, 10));
+
+        assert_eq!(0, queue.gaps(QueueIndexRange::new_empty(5)).count());
+    }
+
+    #[test]
+    fn test_gaps_at_numeric_boundaries() {
+        let mut queue = QueueWithIntervals::<u8>::new();
+        queue.enqueue_range(QueueIndexRange::restore(0, 5));
+        queue.enqueue_range(QueueIndexRange::restore(250, 255));
+
+        let result: Vec<(u8, u8)> = queue
+            .gaps(QueueIndexRange::restore(0, 255))
+            .map(|itm| (itm.from_id, itm.to_id))
+            .collect();
+        assert_eq!(vec![(6, 249)], result);
+
+        assert_eq!(244, queue.missing_count(QueueIndexRange::restore(0, 255)));
+    }
+
+    #[test]
+    fn test_first_gap_after() {
+        let mut queue = QueueWithIntervals::new();
+        queue.enqueue_range(QueueIndexRange::restore(10, 20));
+        queue.enqueue_range(QueueIndexRange::restore(25, 30));
+        queue.enqueue_range(QueueIndexRange::restore(40, 50));
+
+        let gap = queue.first_gap_after(0).unwrap();
+        assert_eq!((1, 9), (gap.from_id, gap.to_id));
+
+        let gap = queue.first_gap_after(15).unwrap();
+        assert_eq!((21, 24), (gap.from_id, gap.to_id));
+
+        let gap = queue.first_gap_after(22).unwrap();
+        assert_eq!((23, 24), (gap.from_id, gap.to_id));
+
+        let gap = queue.first_gap_after(26).unwrap();
+        assert_eq!((31, 39), (gap.from_id, gap.to_id));
+
+        assert!(queue.first_gap_after(45).is_none());
+        assert!(queue.first_gap_after(60).is_none());
+        assert!(
+            QueueWithIntervals::<i64>::new()
+                .first_gap_after(0)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_missing_count() {
+        let mut queue = QueueWithIntervals::new();
+        queue.enqueue_range(QueueIndexRange::restore(100, 200));
+        queue.enqueue(250);
+
+        assert_eq!(99, queue.missing_count(QueueIndexRange::restore(100, 300)));
+        assert_eq!(0, queue.missing_count(QueueIndexRange::restore(100, 200)));
+        assert_eq!(10, queue.missing_count(QueueIndexRange::restore(0, 9)));
+        assert_eq!(0, queue.missing_count(QueueIndexRange::new_empty(0)));
+
+        let queue = QueueWithIntervals::<u128>::new();
+        assert_eq!(
+            usize::MAX,
+            queue.missing_count(QueueIndexRange::restore(0, u128::MAX))
+        );
+    }
+
+    #[test]
+    fn test_complement() {
+        let mut queue = QueueWithIntervals::new();
+        queue.enqueue_range(QueueIndexRange::restore(10, 20));
+        queue.enqueue_range(QueueIndexRange::restore(30, 40));
+
+        let result = queue.complement(QueueIndexRange::restore(0, 50));
+        let intervals: Vec<(i64, i64)> = result
+            .intervals_iter()
+            .map(|itm| (itm.from_id, itm.to_id))
+            .collect();
+        assert_eq!(vec![(0, 9), (21, 29), (41, 50)], intervals);
+        assert_eq!(29, result.len());
+
+        let result = queue.complement(QueueIndexRange::new_full());
+        assert_eq!(Some(i64::MIN), result.get_min_id());
+        assert_eq!(Some(i64::MAX), result.get_max_id());
+        assert_eq!(3, result.get_intervals().len());
+
+        assert!(
+            queue
+                .complement(QueueIndexRange::restore(10, 20))
+                .is_empty()
+        );
+        assert!(queue.complement(QueueIndexRange::new_empty(0)).is_empty());
+    }
+
+    macro_rules! test_complement_at_boundaries {
+        ($($name:ident: $t:ty),*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let empty = QueueWithIntervals::<$t>::new();
+
+                    let full = empty.complement(QueueIndexRange::new_full());
+                    assert_eq!(Some(<$t>::MIN), full.get_min_id());
+                    assert_eq!(Some(<$t>::MAX), full.get_max_id());
+                    assert_eq!(1, full.get_intervals().len());
+
+                    assert!(full.complement(QueueIndexRange::new_full()).is_empty());
+
+                    let mut edges = QueueWithIntervals::<$t>::new();
+                    edges.enqueue(<$t>::MIN);
+                    edges.enqueue(<$t>::MAX);
+
+                    let middle = edges.complement(QueueIndexRange::new_full());
+                    assert_eq!(1, middle.get_intervals().len());
+                    assert_eq!(Some(<$t>::MIN + 1), middle.get_min_id());
+                    assert_eq!(Some(<$t>::MAX - 1), middle.get_max_id());
+
+                    edges.invert_within(QueueIndexRange::new_full());
+                    assert_eq!(middle.get_intervals().len(), edges.get_intervals().len());
+                    assert_eq!(middle.get_min_id(), edges.get_min_id());
+                    assert_eq!(middle.get_max_id(), edges.get_max_id());
+                    assert_eq!(middle.len_u128(), edges.len_u128());
+                }
+            )*
+        };
+    }
+
+    test_complement_at_boundaries!(
+        complement_at_boundaries_i8: i8,
+        complement_at_boundaries_i16: i16,
+        complement_at_boundaries_i32: i32,
+        complement_at_boundaries_i64: i64,
+        complement_at_boundaries_i128: i128,
+        complement_at_boundaries_isize: isize,
+        complement_at_boundaries_u8: u8,
+        complement_at_boundaries_u16: u16,
+        complement_at_boundaries_u32: u32,
+        complement_at_boundaries_u64: u64,
+        complement_at_boundaries_u128: u128,
+        complement_at_boundaries_usize: usize
+    );
+
+    #[test]
+    fn test_complement_of_full_u128_range_has_saturated_len() {
+        let queue = QueueWithIntervals::<u128>::new();
+        let full = queue.complement(QueueIndexRange::new_full());
+        assert_eq!(u128::MAX, full.len_u128());
+    }
+
+    #[test]
+    fn test_invert_within() {
+        let mut queue = QueueWithIntervals::new();
+        queue.enqueue_range(QueueIndexRange::restore(0, 5));
+        queue.enqueue_range(QueueIndexRange::restore(10, 20));
+        queue.enqueue_range(QueueIndexRange::restore(40, 50));
+
+        queue.invert_within(QueueIndexRange::restore(3, 15));
+
+        let intervals: Vec<(i64, i64)> = queue
+            .intervals_iter()
+            .map(|itm| (itm.from_id, itm.to_id))
+            .collect();
+        assert_eq!(vec![(0, 2), (6, 9), (16, 20), (40, 50)], intervals);
+        assert_eq!(23, queue.len());
+
+        queue.invert_within(QueueIndexRange::restore(3, 15));
+        let intervals: Vec<(i64, i64)> = queue
+            .intervals_iter()
+            .map(|itm| (itm.from_id, itm.to_id))
+            .collect();
+        assert_eq!(vec![(0, 5), (10, 20), (40, 50)], intervals);
+    }
+
+    #[test]
+    fn test_invert_unsigned_zero() {
+        let mut queue = QueueWithIntervals::<u32>::new();
+        queue.enqueue(0);
+
+        queue.invert_within(QueueIndexRange::restore(0, 0));
+        assert!(queue.is_empty());
+        assert_eq!(0, queue.len());
+
+        queue.invert_within(QueueIndexRange::restore(0, 3));
+        assert_eq!(Some(0), queue.get_min_id());
+        assert_eq!(Some(3), queue.get_max_id());
+        assert_eq!(4, queue.len());
+    }
+}
@@ -16,166 +16,75 @@ pub enum IndexRange {
 }
 impl IndexRange {
     pub fn new<T: QueueValue>(
-        intervals: &Vec<QueueIndexRange<T>>,
+        intervals: &[QueueIndexRange<T>],
         other_range: &QueueIndexRange<T>,
     ) -> (Self, Self) {
-        let mut from_index = None;
-        let mut to_index = None;
-
-        let mut index = 0;
-
-        let mut prev_interval: Option<&QueueIndexRange<T>> = None;
-
-        for interval in intervals {
-            match &prev_interval {
-                Some(prev_interval) => {
-                    if let (Some(prev_to_plus_one), Some(other_from_plus_one)) = (
-                        prev_interval.to_id.checked_add_one(),
-                        other_range.from_id.checked_add_one(),
-                    ) {
-                        if prev_to_plus_one == other_range.from_id
-                            && other_from_plus_one == interval.from_id
-                        {
-                            from_index = Self::MergeIntervals(index - 1).into();
-                        }
-                    }
-
-                    if let (Some(prev_to_plus_one), Some(other_to_plus_one)) = (
-                        prev_interval.to_id.checked_add_one(),
-                        other_range.to_id.checked_add_one(),
-                    ) {
-                        if prev_to_plus_one == other_range.to_id
-                            && other_to_plus_one == interval.from_id
-                        {
-                            to_index = Self::MergeIntervals(index - 1).into();
-                        }
-                    }
-
-                    if from_index.is_some() && to_index.is_some() {
-                        break;
-                    }
-
-                    if from_index.is_none() {
-                        if let Some(prev_to_plus_one) = prev_interval.to_id.checked_add_one() {
-                            if prev_to_plus_one == other_range.to_id {
-                                to_index = Some(Self::JoinToIndexTo(index))
-                            } else if prev_to_plus_one == other_range.from_id {
-                                from_index = Some(IndexRange::JoinToIndexTo(index - 1))
-                            }
-                        }
-                        if from_index.is_none() {
-                            if prev_interval.to_id < other_range.from_id
-                                && other_range.from_id < interval.from_id
-                            {
-                                from_index = Some(IndexRange::Between {
-                                    left_index: index - 1,
-                                    right_index: index,
-                                });
-                            }
-                        }
-                    }
-
-                    if to_index.is_none() {
-                        if let Some(other_to_plus_one) = other_range.to_id.checked_add_one() {
-                            if other_to_plus_one == interval.from_id {
-                                if let Some(prev_to_plus_one) =
-                                    prev_interval.to_id.checked_add_one()
-                                {
-                                    if prev_to_plus_one == other_range.to_id {
-                                        to_index = Some(IndexRange::MergeIntervals(index - 1))
-                                    } else {
-                                        to_index = Some(IndexRange::JoinToIndexFrom(index))
-                                    }
-                                } else {
-                                    to_index = Some(IndexRange::JoinToIndexFrom(index))
-                                }
-                            }
-                        }
-                        if to_index.is_none() {
-                            if let Some(prev_to_plus_one) = prev_interval.to_id.checked_add_one() {
-                                if prev_to_plus_one == other_range.to_id {
-                                    to_index = Some(Self::JoinToIndexTo(index - 1))
-                                }
-                            }
-                        }
-                        if to_index.is_none() {
-                            if prev_interval.to_id < other_range.to_id
-                                && other_range.to_id < interval.from_id
-                            {
-                                to_index = Some(IndexRange::Between {
-                                    left_index: index - 1,
-                                    right_index: index,
-                                });
-                            }
-                        }
-                    }
-                }
-                None => {
-                    if let Some(other_from_plus_one) = other_range.from_id.checked_add_one() {
-                        if other_from_plus_one == interval.from_id {
-                            from_index = Some(IndexRange::JoinToIndexFrom(0));
-                        }
-                    }
-                    if from_index.is_none() {
-                        if other_range.from_id < interval.from_id {
-                            from_index = Some(IndexRange::First);
-                        }
-                    }
-
-                    if let Some(other_to_plus_one) = other_range.to_id.checked_add_one() {
-                        if other_to_plus_one == interval.from_id {
-                            to_index = Some(IndexRange::JoinToIndexFrom(0));
-                        }
-                    }
-                    if to_index.is_none() {
-                        if let Some(interval_to_plus_one) = interval.to_id.checked_add_one() {
-                            if interval_to_plus_one == other_range.to_id {
-                                to_index = Some(IndexRange::JoinToIndexTo(0));
-                            }
-                        }
-                    }
-                    if to_index.is_none() {
-                        if other_range.to_id < interval.from_id {
-                            to_index = Some(IndexRange::First);
-                        }
-                    }
-                }
-            }
+        let from_index = Self::locate(intervals, other_range.from_id, false);
+        let to_index = Self::locate(intervals, other_range.to_id, true);
+        (from_index, to_index)
+    }
+
+    // Range start and range end are classified the same way, except for a value which only joins
+    // the next interval: the end of the range joins it, while the start of the range stays Between
+    fn locate<T: QueueValue>(
+        intervals: &[QueueIndexRange<T>],
+        value: T,
+        is_range_end: bool,
+    ) -> Self {
+        let index = intervals.partition_point(|itm| itm.to_id < value);
 
-            if interval.is_in_my_interval(other_range.from_id) {
-                from_index = Some(IndexRange::Exact(index));
+        if let Some(interval) = intervals.get(index)
+            && interval.from_id <= value
+        {
+            return Self::Exact(index);
+        }
+
+        if index == 0 {
+            if Self::joins_from_the_left(intervals.first(), value) {
+                return Self::JoinToIndexFrom(0);
             }
+            return Self::First;
+        }
 
-            if interval.is_in_my_interval(other_range.to_id) {
-                to_index = Some(IndexRange::Exact(index));
+        let joins_left = Self::joins_from_the_right(intervals.get(index - 1), value);
+
+        if index == intervals.len() {
+            if joins_left {
+                return Self::JoinToIndexTo(index - 1);
             }
+            return Self::Last;
+        }
 
-            prev_interval = Some(&interval);
+        let joins_right = Self::joins_from_the_left(intervals.get(index), value);
 
-            index += 1;
+        match (joins_left, joins_right) {
+            (true, true) => Self::MergeIntervals(index - 1),
+            (false, true) if is_range_end => Self::JoinToIndexFrom(index),
+            (true, false) => Self::JoinToIndexTo(index - 1),
+            _ => Self::Between {
+                left_index: index - 1,
+                right_index: index,
+            },
         }
+    }
 
-        let to_index = match to_index {
-            Some(to_index) => to_index,
-            None => IndexRange::Last,
-        };
-
-        let from_index = match from_index {
-            Some(from_index) => from_index,
-            None => {
-                if let Some(last_to_plus_one) = intervals.last().unwrap().to_id.checked_add_one() {
-                    if last_to_plus_one == other_range.from_id {
-                        IndexRange::JoinToIndexTo(intervals.len() - 1)
-                    } else {
-                        IndexRange::Last
-                    }
-                } else {
-                    IndexRange::Last
-                }
-            }
-        };
+    // value + 1 == interval.from_id
+    fn joins_from_the_left<T: QueueValue>(interval: Option<&QueueIndexRange<T>>, value: T) -> bool {
+        match (interval, value.checked_add_one()) {
+            (Some(interval), Some(value_plus_one)) => value_plus_one == interval.from_id,
+            _ => false,
+        }
+    }
 
-        (from_index, to_index)
+    // interval.to_id + 1 == value
+    fn joins_from_the_right<T: QueueValue>(
+        interval: Option<&QueueIndexRange<T>>,
+        value: T,
+    ) -> bool {
+        match interval.and_then(|interval| interval.to_id.checked_add_one()) {
+            Some(to_plus_one) => to_plus_one == value,
+            None => false,
+        }
     }
 
     #[cfg(test)]
@@ -207,10 +116,7 @@ impl IndexRange {
 
     #[cfg(test)]
     pub fn is_first(&self) -> bool {
-        match self {
-            Self::First => true,
-            _ => false,
-        }
+        matches!(self, Self::First)
     }
 
     #[cfg(test)]
@@ -231,10 +137,7 @@ impl IndexRange {
 
     #[cfg(test)]
     pub fn is_last(&self) -> bool {
-        match self {
-            Self::Last => true,
-            _ => false,
-        }
+        matches!(self, Self::Last)
     }
 }
 
@@ -703,7 +606,7 @@ mod tests {
 
         let range = QueueIndexRange::restore(5, 9);
         let (from_index, to_index) = IndexRange::new(&intervals, &range);
-        assert_eq!(from_index.is_first(), true);
+        assert!(from_index.is_first());
         assert_eq!(to_index.unwrap_as_join_to_index_from(), 0);
 
         let range = QueueIndexRange::restore(9, 21);
@@ -735,7 +638,7 @@ mod tests {
         let index = QueueIndexRange::restore(61, 70);
         let (from_index, to_index) = IndexRange::new(&intervals, &index);
         assert_eq!(from_index.unwrap_as_join_to_index_to(), 2);
-        assert_eq!(to_index.is_last(), true);
+        assert!(to_index.is_last());
     }
 
     #[test]
@@ -755,7 +658,7 @@ mod tests {
         let index = QueueIndexRange::restore(61, 68);
         let (from_index, to_index) = IndexRange::new(&intervals, &index);
         assert_eq!(from_index.unwrap_as_merge_intervals(), 2);
-        assert_eq!(to_index.is_last(), true);
+        assert!(to_index.is_last());
     }
 
     #[test]
@@ -793,4 +696,36 @@ mod tests {
         assert_eq!(from_index.unwrap_as_merge_intervals(), 0);
         assert_eq!(to_index.unwrap_as_join_to_index_to(), 2);
     }
+
+    #[test]
+    fn test_single_value_joining_previous_interval() {
+        //Preparing data
+        let intervals = vec![
+            QueueIndexRange::restore(10, 20),
+            QueueIndexRange::restore(30, 40),
+        ];
+
+        let other_range = QueueIndexRange::restore(21, 21);
+
+        let (from_index, to_index) = IndexRange::new(&intervals, &other_range);
+
+        assert_eq!(from_index.unwrap_as_join_to_index_to(), 0);
+        assert_eq!(to_index.unwrap_as_join_to_index_to(), 0);
+    }
+
+    #[test]
+    fn test_join_to_index_to_after_last_interval() {
+        //Preparing data
+        let intervals = vec![
+            QueueIndexRange::restore(10, 20),
+            QueueIndexRange::restore(30, 40),
+        ];
+
+        let other_range = QueueIndexRange::restore(35, 41);
+
+        let (from_index, to_index) = IndexRange::new(&intervals, &other_range);
+
+        assert_eq!(from_index.unwrap_as_exact(), 1);
+        assert_eq!(to_index.unwrap_as_join_to_index_to(), 1);
+    }
 }
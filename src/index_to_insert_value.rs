@@ -11,7 +11,7 @@ pub enum IndexToInsertValue {
 }
 
 impl IndexToInsertValue {
-    pub fn new<T: QueueValue>(intervals: &Vec<QueueIndexRange<T>>, value: T) -> Self {
+    pub fn new<T: QueueValue>(intervals: &[QueueIndexRange<T>], value: T) -> Self {
         let result = Self::detect_interval(intervals, value);
 
         match &result {
@@ -24,10 +24,10 @@ impl IndexToInsertValue {
                     if let (Some(left_to_plus_one), Some(value_plus_one)) = (
                         left_interval.to_id.checked_add_one(),
                         value.checked_add_one(),
-                    ) {
-                        if left_to_plus_one == value && value_plus_one == right_interval.from_id {
-                            return Self::MergeTwoIntervals(index - 1);
-                        }
+                    ) && left_to_plus_one == value
+                        && value_plus_one == right_interval.from_id
+                    {
+                        return Self::MergeTwoIntervals(index - 1);
                     }
                 }
             }
@@ -40,10 +40,10 @@ impl IndexToInsertValue {
                     if let (Some(left_to_plus_one), Some(value_plus_one)) = (
                         left_interval.to_id.checked_add_one(),
                         value.checked_add_one(),
-                    ) {
-                        if left_to_plus_one == value && value_plus_one == right_interval.from_id {
-                            return Self::MergeTwoIntervals(index);
-                        }
+                    ) && left_to_plus_one == value
+                        && value_plus_one == right_interval.from_id
+                    {
+                        return Self::MergeTwoIntervals(index);
                     }
                 }
             }
@@ -56,42 +56,31 @@ impl IndexToInsertValue {
     }
 
     fn detect_interval<T: QueueValue>(intervals: &[QueueIndexRange<T>], value: T) -> Self {
-        let mut prev_element: Option<&QueueIndexRange<T>> = None;
-
-        let mut index = 0;
-        for itm in intervals {
-            if itm.from_id <= value && value <= itm.to_id {
-                return Self::HasValue;
-            }
-
-            if let Some(value_plus_one) = value.checked_add_one() {
-                if value_plus_one == itm.from_id {
-                    return Self::MergeToLeft(index);
-                }
-            }
-
-            if let Some(to_plus_one) = itm.to_id.checked_add_one() {
-                if to_plus_one == value {
-                    return Self::MergeToRight(index);
-                }
-            }
-
-            if let Some(prev_element) = prev_element {
-                if prev_element.to_id < value && value < itm.from_id {
-                    return Self::InsertAsNewInterval(index);
-                }
-            } else {
-                if value < itm.from_id {
-                    return Self::InsertAsNewInterval(0);
-                }
-            }
+        // Intervals are sorted and do not overlap, so the first interval whose to_id is not below
+        // the value is the only one which can contain it or be joined to it from the left.
+        let index = intervals.partition_point(|itm| itm.to_id < value);
+
+        if let Some(itm) = intervals.get(index)
+            && itm.from_id <= value
+        {
+            return Self::HasValue;
+        }
 
-            prev_element = Some(itm);
+        if index > 0
+            && let Some(to_plus_one) = intervals[index - 1].to_id.checked_add_one()
+            && to_plus_one == value
+        {
+            return Self::MergeToRight(index - 1);
+        }
 
-            index += 1;
+        if let Some(itm) = intervals.get(index)
+            && let Some(value_plus_one) = value.checked_add_one()
+            && value_plus_one == itm.from_id
+        {
+            return Self::MergeToLeft(index);
         }
 
-        Self::InsertAsNewInterval(intervals.len())
+        Self::InsertAsNewInterval(index)
     }
 
     pub fn unwrap_as_merge_to_right(&self) -> usize {
@@ -14,51 +14,49 @@ pub enum IndexToRemoveValue<T: QueueValue> {
 }
 
 impl<T: QueueValue> IndexToRemoveValue<T> {
-    pub fn new(intervals: &Vec<QueueIndexRange<T>>, value: T) -> Self {
-        let mut index = 0;
-        for interval in intervals {
-            if interval.from_id == value {
-                if interval.to_id == value {
-                    return Self::Remove(index);
-                }
-                return Self::IncLeft(index);
-            }
+    pub fn new(intervals: &[QueueIndexRange<T>], value: T) -> Self {
+        let index = intervals.partition_point(|itm| itm.to_id < value);
+
+        let Some(interval) = intervals.get(index) else {
+            return Self::NoValue;
+        };
 
+        if interval.from_id == value {
             if interval.to_id == value {
-                return Self::DecRight(index);
+                return Self::Remove(index);
             }
+            return Self::IncLeft(index);
+        }
 
-            if interval.from_id < value && value < interval.to_id {
-                if let (Some(value_minus_one), Some(value_plus_one)) =
-                    (value.checked_sub_one(), value.checked_add_one())
-                {
-                    return Self::Split {
-                        index,
-                        left: QueueIndexRange {
-                            from_id: interval.from_id,
-                            to_id: value_minus_one,
-                        },
-                        right: QueueIndexRange {
-                            from_id: value_plus_one,
-                            to_id: interval.to_id,
-                        },
-                    };
-                } else {
-                    panic!("Cannot split interval at value (overflow/underflow)");
-                }
-            }
+        if interval.to_id == value {
+            return Self::DecRight(index);
+        }
 
-            index += 1;
+        if interval.from_id < value && value < interval.to_id {
+            if let (Some(value_minus_one), Some(value_plus_one)) =
+                (value.checked_sub_one(), value.checked_add_one())
+            {
+                return Self::Split {
+                    index,
+                    left: QueueIndexRange {
+                        from_id: interval.from_id,
+                        to_id: value_minus_one,
+                    },
+                    right: QueueIndexRange {
+                        from_id: value_plus_one,
+                        to_id: interval.to_id,
+                    },
+                };
+            } else {
+                panic!("Cannot split interval at value (overflow/underflow)");
+            }
         }
 
         Self::NoValue
     }
 
     pub fn is_no_value(&self) -> bool {
-        match self {
-            IndexToRemoveValue::NoValue => true,
-            _ => false,
-        }
+        matches!(self, IndexToRemoveValue::NoValue)
     }
 
     pub fn unwrap_as_inc_left(&self) -> usize {
@@ -115,7 +113,7 @@ mod tests {
             },
         ];
 
-        assert_eq!(IndexToRemoveValue::new(&intervals, 5).is_no_value(), true);
+        assert!(IndexToRemoveValue::new(&intervals, 5).is_no_value());
 
         assert_eq!(
             IndexToRemoveValue::new(&intervals, 10).unwrap_as_inc_left(),
@@ -0,0 +1,141 @@
+use super::{QueueIndexRange, QueueWithIntervals, queue_with_intervals::saturate_count};
+use crate::QueueValue;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InsertOutcome {
+    // Amount of values which were not in the queue before, saturating at usize::MAX
+    pub new_values: usize,
+    // Amount of values which were already in the queue, saturating at usize::MAX
+    pub already_present: usize,
+}
+
+impl<T: QueueValue> QueueWithIntervals<T> {
+    /// Enqueues the value. Returns false if the value is already in the queue
+    pub fn insert(&mut self, value: T) -> bool {
+        match self.try_enqueue(value) {
+            Ok(is_new) => is_new,
+            Err(err) => panic!("Can not insert value {}: {:?}", value, err),
+        }
+    }
+
+    /// Enqueues the range and reports how many of its values were new
+    pub fn insert_range(&mut self, range: impl Into<QueueIndexRange<T>>) -> InsertOutcome {
+        let range = range.into();
+
+        if range.is_empty() {
+            return InsertOutcome {
+                new_values: 0,
+                already_present: 0,
+            };
+        }
+
+        let range_count = range.values_count();
+
+        let (new_values, already_present) = match self.try_enqueue_range_counting(range) {
+            // Some values were new, so fewer than 2^128 values were already present
+            Ok(Some(new_values)) => (
+                saturate_count(new_values, true),
+                saturate_count(range_count.wrapping_sub(new_values), false),
+            ),
+            Ok(None) => (0, saturate_count(range_count, true)),
+            Err(err) => panic!("Can not enqueue range: {:?}", err),
+        };
+
+        InsertOutcome {
+            new_values,
+            already_present,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::InsertOutcome;
+    use crate::{QueueIndexRange, QueueWithIntervals};
+
+    #[test]
+    fn test_insert() {
+        let mut queue = QueueWithIntervals::new();
+
+        assert!(queue.insert(5));
+        assert!(queue.insert(6));
+        assert!(!queue.insert(5));
+        assert!(queue.insert(i64::MIN));
+        assert!(!queue.insert(i64::MIN));
+
+        assert_eq!(3, queue.len());
+    }
+
+    #[test]
+    fn test_insert_range() {
+        let mut queue = QueueWithIntervals::new();
+
+        assert_eq!(
+            InsertOutcome {
+                new_values: 11,
+                already_present: 0
+            },
+            queue.insert_range(QueueIndexRange::restore(10, 20))
+        );
+
+        queue.enqueue_range(QueueIndexRange::restore(30, 40));
+
+        assert_eq!(
+            InsertOutcome {
+                new_values: 9,
+                already_present: 22
+            },
+            queue.insert_range(QueueIndexRange::restore(10, 40))
+        );
+
+        assert_eq!(
+            InsertOutcome {
+                new_values: 0,
+                already_present: 5
+            },
+            queue.insert_range(QueueIndexRange::restore(12, 16))
+        );
+
+        assert_eq!(
+            InsertOutcome {
+                new_values: 0,
+                already_present: 0
+            },
+            queue.insert_range(QueueIndexRange::new_empty(12))
+        );
+
+        assert_eq!(31, queue.len());
+    }
+
+    #[test]
+    fn test_insert_full_range() {
+        let mut queue = QueueWithIntervals::<u128>::new();
+
+        assert_eq!(
+            InsertOutcome {
+                new_values: usize::MAX,
+                already_present: 0
+            },
+            queue.insert_range(QueueIndexRange::new_full())
+        );
+
+        assert_eq!(
+            InsertOutcome {
+                new_values: 0,
+                already_present: usize::MAX
+            },
+            queue.insert_range(QueueIndexRange::new_full())
+        );
+
+        let mut queue = QueueWithIntervals::<u8>::new();
+        queue.enqueue_range(QueueIndexRange::restore(0, 99));
+
+        assert_eq!(
+            InsertOutcome {
+                new_values: 156,
+                already_present: 100
+            },
+            queue.insert_range(QueueIndexRange::new_full())
+        );
+    }
+}
@@ -0,0 +1,252 @@
+use std::fmt;
+
+use super::{QueueIndexRange, QueueWithIntervals};
+use crate::QueueValue;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InvariantViolation<T: QueueValue = i64> {
+    // Queue has no intervals at all. An empty queue must keep a single empty interval
+    NoIntervals,
+    // Interval has from_id greater than to_id
+    EmptyInterval {
+        index: usize,
+        from_id: T,
+        to_id: T,
+    },
+    // Interval starts before the previous one
+    Unsorted {
+        index: usize,
+        from_id: T,
+        previous_from_id: T,
+    },
+    // Interval shares values with the previous one
+    Overlapping {
+        index: usize,
+        from_id: T,
+        previous_to_id: T,
+    },
+    // Interval starts right after the previous one, so they must be a single interval
+    Adjacent {
+        index: usize,
+        from_id: T,
+        previous_to_id: T,
+    },
+    // Cached amount of values differs from the amount of values in the intervals
+    CountMismatch {
+        cached: u128,
+        actual: u128,
+    },
+}
+
+impl<T: QueueValue> fmt::Display for InvariantViolation<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvariantViolation::NoIntervals => write!(f, "Queue has no intervals"),
+            InvariantViolation::EmptyInterval {
+                index,
+                from_id,
+                to_id,
+            } => write!(f, "Interval #{} [{}, {}] is empty", index, from_id, to_id),
+            InvariantViolation::Unsorted {
+                index,
+                from_id,
+                previous_from_id,
+            } => write!(
+                f,
+                "Interval #{} starts at {} before the previous interval which starts at {}",
+                index, from_id, previous_from_id
+            ),
+            InvariantViolation::Overlapping {
+                index,
+                from_id,
+                previous_to_id,
+            } => write!(
+                f,
+                "Interval #{} starts at {} and overlaps the previous interval which ends at {}",
+                index, from_id, previous_to_id
+            ),
+            InvariantViolation::Adjacent {
+                index,
+                from_id,
+                previous_to_id,
+            } => write!(
+                f,
+                "Interval #{} starts at {} right after the previous interval which ends at {}",
+                index, from_id, previous_to_id
+            ),
+            InvariantViolation::CountMismatch { cached, actual } => write!(
+                f,
+                "Cached amount of values {} differs from the actual amount {}",
+                cached, actual
+            ),
+        }
+    }
+}
+
+impl<T: QueueValue> std::error::Error for InvariantViolation<T> {}
+
+impl<T: QueueValue> QueueWithIntervals<T> {
+    /// Checks that intervals are sorted, not overlapping and not adjacent,
+    /// that only an empty queue has an empty interval and that the cached amount of values is correct
+    pub fn validate(&self) -> Result<(), InvariantViolation<T>> {
+        if self.intervals.is_empty() {
+            return Err(InvariantViolation::NoIntervals);
+        }
+
+        if !self.is_empty() {
+            check_intervals(&self.intervals)?;
+        }
+
+        let actual = self
+            .get_non_empty_intervals()
+            .iter()
+            .fold(0u128, |acc, itm| acc.wrapping_add(itm.values_count()));
+
+        if actual != self.values_count {
+            return Err(InvariantViolation::CountMismatch {
+                cached: self.values_count,
+                actual,
+            });
+        }
+
+        Ok(())
+    }
+
+    // Validates the queue after a mutating operation when the check-invariants feature is enabled
+    #[cfg(feature = "check-invariants")]
+    pub(crate) fn check_invariants(&self, operation: &str) {
+        if let Err(err) = self.validate() {
+            panic!(
+                "{} broke the queue: {}. Intervals: {:?}",
+                operation, err, self.intervals
+            );
+        }
+    }
+
+    #[cfg(not(feature = "check-invariants"))]
+    #[inline(always)]
+    pub(crate) fn check_invariants(&self, _operation: &str) {}
+}
+
+// Checks that intervals are not empty, sorted, not overlapping and not adjacent
+pub(crate) fn check_intervals<T: QueueValue>(
+    intervals: &[QueueIndexRange<T>],
+) -> Result<(), InvariantViolation<T>> {
+    for (index, interval) in intervals.iter().enumerate() {
+        if interval.is_empty() {
+            return Err(InvariantViolation::EmptyInterval {
+                index,
+                from_id: interval.from_id,
+                to_id: interval.to_id,
+            });
+        }
+
+        if index == 0 {
+            continue;
+        }
+
+        let previous = &intervals[index - 1];
+
+        if interval.from_id < previous.from_id {
+            return Err(InvariantViolation::Unsorted {
+                index,
+                from_id: interval.from_id,
+                previous_from_id: previous.from_id,
+            });
+        }
+
+        if interval.from_id <= previous.to_id {
+            return Err(InvariantViolation::Overlapping {
+                index,
+                from_id: interval.from_id,
+                previous_to_id: previous.to_id,
+            });
+        }
+
+        if previous.to_id.checked_add_one() == Some(interval.from_id) {
+            return Err(InvariantViolation::Adjacent {
+                index,
+                from_id: interval.from_id,
+                previous_to_id: previous.to_id,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::InvariantViolation;
+    use crate::{QueueIndexRange, QueueWithIntervals};
+
+    #[test]
+    fn test_valid_queues() {
+        let mut queue = QueueWithIntervals::new();
+        assert_eq!(Ok(()), queue.validate());
+
+        queue.enqueue_range(QueueIndexRange::restore(10, 20));
+        queue.enqueue_range(QueueIndexRange::restore(30, 40));
+        assert_eq!(Ok(()), queue.validate());
+
+        queue.clean();
+        assert_eq!(Ok(()), queue.validate());
+
+        let full = QueueWithIntervals::<u128>::from_single_interval(0, u128::MAX);
+        assert_eq!(Ok(()), full.validate());
+    }
+
+    #[test]
+    fn test_broken_queues() {
+        let mut queue = QueueWithIntervals::new();
+        queue.enqueue_range(QueueIndexRange::restore(10, 20));
+        queue.enqueue_range(QueueIndexRange::restore(30, 40));
+
+        let mut broken = queue.clone();
+        broken.intervals.clear();
+        assert_eq!(Err(InvariantViolation::NoIntervals), broken.validate());
+
+        let mut broken = queue.clone();
+        broken.intervals[1].from_id = 21;
+        assert_eq!(
+            Err(InvariantViolation::Adjacent {
+                index: 1,
+                from_id: 21,
+                previous_to_id: 20
+            }),
+            broken.validate()
+        );
+
+        let mut broken = queue.clone();
+        broken.intervals.push(QueueIndexRange::new_empty(50));
+        assert_eq!(
+            Err(InvariantViolation::EmptyInterval {
+                index: 2,
+                from_id: 50,
+                to_id: 49
+            }),
+            broken.validate()
+        );
+
+        let mut broken = queue.clone();
+        broken.values_count = 5;
+        assert_eq!(
+            Err(InvariantViolation::CountMismatch {
+                cached: 5,
+                actual: 22
+            }),
+            broken.validate()
+        );
+    }
+
+    #[cfg(feature = "check-invariants")]
+    #[test]
+    #[should_panic(expected = "reset broke the queue")]
+    fn test_mutation_breaking_invariants_panics() {
+        let mut queue = QueueWithIntervals::new();
+        queue.reset(vec![
+            QueueIndexRange::restore(10, 20),
+            QueueIndexRange::restore(15, 30),
+        ]);
+    }
+}
@@ -1,4 +1,6 @@
-use super::{QueueIndexRange, QueueWithIntervals};
+use std::iter::FusedIterator;
+
+use super::{QueueIndexRange, QueueWithIntervals, queue_with_intervals::saturate_count};
 use crate::QueueValue;
 
 pub struct QueueWithIntervalsIterator<T: QueueValue> {
@@ -15,10 +17,217 @@ impl<T: QueueValue> Iterator for QueueWithIntervalsIterator<T> {
     type Item = T;
 
     fn next(&mut self) -> Option<T> {
-        return self.intervals.dequeue();
+        self.intervals.dequeue()
+    }
+}
+
+pub struct QueueWithIntervalsRefIterator<'s, T: QueueValue> {
+    intervals: &'s [QueueIndexRange<T>],
+    front_index: usize,
+    front_id: T,
+    back_index: usize,
+    back_id: T,
+    // Values left to yield modulo 2^128. Zero while not finished means 2^128 values are left
+    remaining: u128,
+    finished: bool,
+}
+
+impl<'s, T: QueueValue> QueueWithIntervalsRefIterator<'s, T> {
+    /// Iterates every value of the sorted intervals, which hold values_count values.
+    /// Empty intervals kept by restore and reset are skipped
+    pub(crate) fn new(intervals: &'s [QueueIndexRange<T>], values_count: u128) -> Self {
+        // Both edge intervals of a queue are not empty unless it was restored, so this is O(1) for the rest
+        let front_index = intervals.iter().position(|itm| !itm.is_empty());
+        let back_index = intervals.iter().rposition(|itm| !itm.is_empty());
+
+        match (front_index, back_index) {
+            (Some(front_index), Some(back_index)) => Self {
+                intervals,
+                front_index,
+                front_id: intervals[front_index].from_id,
+                back_index,
+                back_id: intervals[back_index].to_id,
+                remaining: values_count,
+                finished: false,
+            },
+            _ => Self::new_finished(intervals),
+        }
+    }
+
+    /// Iterates only values within [from_id, to_id]
+    pub(crate) fn new_in_bounds(intervals: &'s [QueueIndexRange<T>], from_id: T, to_id: T) -> Self {
+        let front_index = intervals
+            .iter()
+            .position(|itm| !itm.clip(from_id, to_id).is_empty());
+        let back_index = intervals
+            .iter()
+            .rposition(|itm| !itm.clip(from_id, to_id).is_empty());
+
+        match (front_index, back_index) {
+            (Some(front_index), Some(back_index)) => Self {
+                intervals,
+                front_index,
+                front_id: intervals[front_index].clip(from_id, to_id).from_id,
+                back_index,
+                back_id: intervals[back_index].clip(from_id, to_id).to_id,
+                remaining: intervals.iter().fold(0u128, |acc, itm| {
+                    acc.wrapping_add(itm.clip(from_id, to_id).values_count())
+                }),
+                finished: false,
+            },
+            _ => Self::new_finished(intervals),
+        }
+    }
+
+    fn new_finished(intervals: &'s [QueueIndexRange<T>]) -> Self {
+        Self {
+            intervals,
+            front_index: 0,
+            front_id: T::zero(),
+            back_index: 0,
+            back_id: T::zero(),
+            remaining: 0,
+            finished: true,
+        }
+    }
+
+    fn is_last_value(&self) -> bool {
+        self.front_index == self.back_index && self.front_id == self.back_id
+    }
+}
+
+impl<T: QueueValue> Iterator for QueueWithIntervalsRefIterator<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.finished {
+            return None;
+        }
+
+        let result = self.front_id;
+        self.remaining = self.remaining.wrapping_sub(1);
+
+        if self.is_last_value() {
+            self.finished = true;
+            return Some(result);
+        }
+
+        if self.front_id == self.intervals[self.front_index].to_id {
+            self.front_index += 1;
+            while self.intervals[self.front_index].is_empty() {
+                self.front_index += 1;
+            }
+            self.front_id = self.intervals[self.front_index].from_id;
+        } else {
+            self.front_id += T::one();
+        }
+
+        Some(result)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.finished {
+            return (0, Some(0));
+        }
+
+        match saturate_count(self.remaining, true) {
+            usize::MAX => (usize::MAX, None),
+            remaining => (remaining, Some(remaining)),
+        }
+    }
+}
+
+impl<T: QueueValue> DoubleEndedIterator for QueueWithIntervalsRefIterator<'_, T> {
+    fn next_back(&mut self) -> Option<T> {
+        if self.finished {
+            return None;
+        }
+
+        let result = self.back_id;
+        self.remaining = self.remaining.wrapping_sub(1);
+
+        if self.is_last_value() {
+            self.finished = true;
+            return Some(result);
+        }
+
+        if self.back_id == self.intervals[self.back_index].from_id {
+            self.back_index -= 1;
+            while self.intervals[self.back_index].is_empty() {
+                self.back_index -= 1;
+            }
+            self.back_id = self.intervals[self.back_index].to_id;
+        } else {
+            self.back_id -= T::one();
+        }
+
+        Some(result)
+    }
+}
+
+impl<T: QueueValue> FusedIterator for QueueWithIntervalsRefIterator<'_, T> {}
+
+// The amount of values always fits into usize only for these types
+macro_rules! impl_exact_size_iterator {
+    ($($t:ty),*) => {
+        $(
+            impl ExactSizeIterator for QueueWithIntervalsRefIterator<'_, $t> {}
+        )*
+    };
+}
+
+impl_exact_size_iterator!(i8, i16, u8, u16);
+
+#[cfg(target_pointer_width = "64")]
+impl_exact_size_iterator!(i32, u32);
+
+pub struct QueueIndexRangesIterator<'s, T: QueueValue> {
+    intervals: std::slice::Iter<'s, QueueIndexRange<T>>,
+    from_id: T,
+    to_id: T,
+}
+
+impl<'s, T: QueueValue> QueueIndexRangesIterator<'s, T> {
+    /// Iterates intervals clipped to [from_id, to_id], skipping the ones which end up empty
+    pub(crate) fn new(intervals: &'s [QueueIndexRange<T>], from_id: T, to_id: T) -> Self {
+        Self {
+            intervals: intervals.iter(),
+            from_id,
+            to_id,
+        }
+    }
+}
+
+impl<T: QueueValue> Iterator for QueueIndexRangesIterator<'_, T> {
+    type Item = QueueIndexRange<T>;
+
+    fn next(&mut self) -> Option<QueueIndexRange<T>> {
+        loop {
+            let clipped = self.intervals.next()?.clip(self.from_id, self.to_id);
+            if !clipped.is_empty() {
+                return Some(clipped);
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.intervals.size_hint().1)
+    }
+}
+
+impl<T: QueueValue> DoubleEndedIterator for QueueIndexRangesIterator<'_, T> {
+    fn next_back(&mut self) -> Option<QueueIndexRange<T>> {
+        loop {
+            let clipped = self.intervals.next_back()?.clip(self.from_id, self.to_id);
+            if !clipped.is_empty() {
+                return Some(clipped);
+            }
+        }
     }
 }
 
+impl<T: QueueValue> FusedIterator for QueueIndexRangesIterator<'_, T> {}
+
 pub struct QueueIndexRangeIterator<T: QueueValue> {
     from_id: T,
     to_id: T,
@@ -43,7 +252,7 @@ impl<T: QueueValue> Iterator for QueueIndexRangeIterator<T> {
             } else {
                 // Reached max value
                 self.from_id = self.to_id;
-                if let Some(_) = self.to_id.checked_add_one() {
+                if self.to_id.checked_add_one().is_some() {
                     // This shouldn't happen, but handle it
                     return Some(result);
                 }
@@ -51,7 +260,7 @@ impl<T: QueueValue> Iterator for QueueIndexRangeIterator<T> {
             return Some(result);
         }
 
-        return None;
+        None
     }
 }
 
@@ -113,6 +322,113 @@ mod tests {
         assert_eq!(vec![1, 2, 3, 10, 11, 12], collected);
     }
 
+    #[test]
+    fn test_ref_iterator_does_not_consume_queue() {
+        let mut queue = QueueWithIntervals::new();
+        queue.enqueue_range(QueueIndexRange::restore(1, 3));
+        queue.enqueue_range(QueueIndexRange::restore(10, 12));
+
+        let iter = queue.iter();
+        assert_eq!((6, Some(6)), iter.size_hint());
+
+        let collected: Vec<i64> = iter.collect();
+        assert_eq!(vec![1, 2, 3, 10, 11, 12], collected);
+        assert_eq!(6, queue.len());
+    }
+
+    #[test]
+    fn test_ref_iterator_double_ended() {
+        let mut queue = QueueWithIntervals::new();
+        queue.enqueue_range(QueueIndexRange::restore(1, 3));
+        queue.enqueue_range(QueueIndexRange::restore(10, 12));
+
+        let collected: Vec<i64> = queue.iter().rev().collect();
+        assert_eq!(vec![12, 11, 10, 3, 2, 1], collected);
+
+        let mut iter = queue.iter();
+        assert_eq!(Some(1), iter.next());
+        assert_eq!(Some(12), iter.next_back());
+        assert_eq!(Some(2), iter.next());
+        assert_eq!(Some(11), iter.next_back());
+        assert_eq!(Some(3), iter.next());
+        assert_eq!(Some(10), iter.next_back());
+        assert_eq!((0, Some(0)), iter.size_hint());
+        assert_eq!(None, iter.next());
+        assert_eq!(None, iter.next_back());
+    }
+
+    #[test]
+    fn test_ref_iterator_exact_size() {
+        let mut queue = QueueWithIntervals::<u8>::new();
+        queue.enqueue_range(QueueIndexRange::restore(250, 255));
+        queue.enqueue_range(QueueIndexRange::restore(0, 1));
+
+        let mut iter = queue.iter();
+        assert_eq!(8, iter.len());
+        iter.next();
+        assert_eq!(7, iter.len());
+
+        let collected: Vec<u8> = iter.collect();
+        assert_eq!(vec![1, 250, 251, 252, 253, 254, 255], collected);
+    }
+
+    #[test]
+    fn test_ref_iterator_on_full_range() {
+        let queue = QueueWithIntervals::from_single_interval(u128::MIN, u128::MAX);
+
+        let mut iter = queue.iter();
+        assert_eq!((usize::MAX, None), iter.size_hint());
+        assert_eq!(Some(u128::MIN), iter.next());
+        assert_eq!(Some(u128::MAX), iter.next_back());
+        assert_eq!((usize::MAX, None), iter.size_hint());
+    }
+
+    #[test]
+    fn test_ref_iterator_skips_empty_intervals() {
+        let queue = QueueWithIntervals::restore(vec![
+            QueueIndexRange::restore(3, 4),
+            QueueIndexRange::new_empty(5),
+            QueueIndexRange::restore(7, 7),
+        ]);
+
+        let collected: Vec<i64> = queue.iter().collect();
+        assert_eq!(vec![3, 4, 7], collected);
+
+        let collected: Vec<i64> = queue.iter().rev().collect();
+        assert_eq!(vec![7, 4, 3], collected);
+    }
+
+    #[test]
+    fn test_ref_iterator_skips_leading_and_trailing_empty_intervals() {
+        let queue = QueueWithIntervals::restore(vec![
+            QueueIndexRange::restore(3, 4),
+            QueueIndexRange::new_empty(10),
+        ]);
+        assert_eq!(vec![3, 4], queue.iter().collect::<Vec<i64>>());
+        assert_eq!(vec![4, 3], queue.iter().rev().collect::<Vec<i64>>());
+        assert_eq!((2, Some(2)), queue.iter().size_hint());
+
+        let queue = QueueWithIntervals::restore(vec![
+            QueueIndexRange::new_empty(0),
+            QueueIndexRange::restore(3, 4),
+        ]);
+        assert_eq!(vec![3, 4], queue.iter().collect::<Vec<i64>>());
+        assert_eq!(vec![4, 3], queue.iter().rev().collect::<Vec<i64>>());
+        assert_eq!((2, Some(2)), queue.iter().size_hint());
+    }
+
+    #[test]
+    fn test_ref_iterator_over_only_empty_intervals() {
+        let queue = QueueWithIntervals::<i64>::restore(vec![
+            QueueIndexRange::new_empty(0),
+            QueueIndexRange::new_empty(5),
+        ]);
+
+        assert_eq!(None, queue.iter().next());
+        assert_eq!(None, queue.iter().next_back());
+        assert_eq!((0, Some(0)), queue.iter().size_hint());
+    }
+
     #[test]
     fn test_queue_index_range_iterator() {
         let range = QueueIndexRange::restore(5, 7);
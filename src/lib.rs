@@ -1,10 +1,11 @@
 pub use queue_index_range::QueueIndexRange;
-pub use queue_with_intervals::QueueWithIntervals;
+pub use queue_with_intervals::{QueueWithIntervals, QueueWithIntervalsError};
 
 mod queue_value;
 pub use queue_value::QueueValue;
 
 mod iterator;
+pub use iterator::*;
 mod queue_index_range;
 mod queue_with_intervals;
 
@@ -17,3 +18,25 @@ pub use index_to_remove_value::*;
 mod remove_range;
 pub use remove_range::*;
 mod enqueue_range;
+mod insert;
+pub use insert::*;
+mod invariants;
+pub use invariants::InvariantViolation;
+mod codec;
+pub use codec::*;
+mod comparison;
+mod dequeue_range;
+mod dequeue_while;
+mod from_iterator;
+mod gaps;
+mod range_list;
+pub use range_list::*;
+mod partition;
+pub use partition::ModuloShard;
+mod rank;
+mod set_operations;
+mod split;
+pub use gaps::*;
+
+#[cfg(feature = "serde")]
+mod serde_impl;
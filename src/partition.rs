@@ -0,0 +1,279 @@
+use std::{marker::PhantomData, ops::Bound};
+
+use super::{QueueIndexRange, QueueWithIntervals, set_operations::push_interval};
+use crate::QueueValue;
+
+// Values of a modulo shard are stored as steps: value = T::min_value() + step * modulus + offset_remainder.
+// Every interval longer than the modulus turns into a single interval of steps in each shard
+
+/// Values of a queue which give the same remainder when divided by the modulus
+#[derive(Debug, Clone)]
+pub struct ModuloShard<T: QueueValue = i64> {
+    modulus: u128,
+    remainder: usize,
+    // Remainder of the distance from T::min_value(), which differs from the value remainder for signed types
+    offset_remainder: u128,
+    steps: QueueWithIntervals<u128>,
+    value_type: PhantomData<T>,
+}
+
+impl<T: QueueValue> ModuloShard<T> {
+    pub fn modulus(&self) -> usize {
+        self.modulus as usize
+    }
+
+    /// Euclidean remainder of every value of the shard, so it is never negative
+    pub fn remainder(&self) -> usize {
+        self.remainder
+    }
+
+    /// Returns the amount of values in the shard, saturating at usize::MAX
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let offset = T::distance(T::min_value(), value);
+
+        offset % self.modulus == self.offset_remainder
+            && self.steps.has_message(offset / self.modulus)
+    }
+
+    /// Iterates values of the shard in ascending order
+    pub fn iter(&self) -> impl Iterator<Item = T> + '_ {
+        self.steps.iter().map(|step| self.value_of(step))
+    }
+
+    /// Expands the shard into a queue. Values are not adjacent unless the modulus is one,
+    /// so the queue has an interval per value
+    pub fn to_queue(&self) -> QueueWithIntervals<T> {
+        if self.modulus == 1 {
+            return QueueWithIntervals::from_sorted_intervals(
+                self.steps
+                    .intervals_iter()
+                    .map(|itm| {
+                        QueueIndexRange::restore(
+                            self.value_of(itm.from_id),
+                            self.value_of(itm.to_id),
+                        )
+                    })
+                    .collect(),
+            );
+        }
+
+        QueueWithIntervals::from_sorted_intervals(
+            self.iter()
+                .map(QueueIndexRange::new_with_single_value)
+                .collect(),
+        )
+    }
+
+    fn value_of(&self, step: u128) -> T {
+        T::min_value().add_distance(step * self.modulus + self.offset_remainder)
+    }
+}
+
+impl<T: QueueValue> QueueWithIntervals<T> {
+    /// Splits the queue by the boundaries, which must be sorted. Shard i holds values
+    /// in [boundaries[i - 1], boundaries[i]), so there is one shard more than boundaries
+    pub fn partition_by_ranges(&self, boundaries: &[T]) -> Vec<Self> {
+        assert!(
+            boundaries.is_sorted(),
+            "Partition boundaries must be sorted: {:?}",
+            boundaries
+        );
+
+        let mut result = Vec::with_capacity(boundaries.len() + 1);
+        let mut from = Bound::Unbounded;
+
+        for boundary in boundaries {
+            let intervals = self.range_intervals((from, Bound::Excluded(*boundary)));
+            result.push(Self::from_sorted_intervals(intervals.collect()));
+            from = Bound::Included(*boundary);
+        }
+
+        let intervals = self.range_intervals((from, Bound::Unbounded));
+        result.push(Self::from_sorted_intervals(intervals.collect()));
+
+        result
+    }
+
+    /// Splits the queue into k shards. Shard r holds values whose Euclidean remainder of division by k is r
+    pub fn partition_mod(&self, k: usize) -> Vec<ModuloShard<T>> {
+        assert!(k > 0, "Can not partition a queue into zero shards");
+
+        let modulus = k as u128;
+        // Euclidean remainder of T::min_value(), which is zero for unsigned types
+        let min_remainder = (modulus - T::distance(T::min_value(), T::zero()) % modulus) % modulus;
+
+        let mut steps: Vec<Vec<QueueIndexRange<u128>>> = vec![vec![]; k];
+
+        for interval in self.get_non_empty_intervals() {
+            let from_offset = T::distance(T::min_value(), interval.from_id);
+            let to_offset = T::distance(T::min_value(), interval.to_id);
+            if interval.len_u128() >= modulus {
+                for (offset_remainder, shard) in steps.iter_mut().enumerate() {
+                    if let Some(range) =
+                        steps_in_offsets(from_offset, to_offset, offset_remainder as u128, modulus)
+                    {
+                        push_interval(shard, &range);
+                    }
+                }
+
+                continue;
+            }
+
+            // The interval is shorter than the modulus, so every value goes to a different shard
+            let mut offset = from_offset;
+
+            loop {
+                let step = offset / modulus;
+                push_interval(
+                    &mut steps[(offset % modulus) as usize],
+                    &QueueIndexRange::new_with_single_value(step),
+                );
+
+                if offset == to_offset {
+                    break;
+                }
+
+                offset += 1;
+            }
+        }
+
+        let mut result: Vec<ModuloShard<T>> = steps
+            .into_iter()
+            .enumerate()
+            .map(|(offset_remainder, steps)| ModuloShard {
+                modulus,
+                remainder: ((offset_remainder as u128 + min_remainder) % modulus) as usize,
+                offset_remainder: offset_remainder as u128,
+                steps: QueueWithIntervals::from_sorted_intervals(steps),
+                value_type: PhantomData,
+            })
+            .collect();
+
+        result.sort_by_key(|shard| shard.remainder);
+
+        result
+    }
+}
+
+// Returns the steps of offsets in [from_offset, to_offset] which give the remainder
+fn steps_in_offsets(
+    from_offset: u128,
+    to_offset: u128,
+    offset_remainder: u128,
+    modulus: u128,
+) -> Option<QueueIndexRange<u128>> {
+    if to_offset < offset_remainder {
+        return None;
+    }
+
+    let from_step = if from_offset <= offset_remainder {
+        0
+    } else {
+        let distance = from_offset - offset_remainder;
+        distance / modulus + u128::from(!distance.is_multiple_of(modulus))
+    };
+
+    let to_step = (to_offset - offset_remainder) / modulus;
+
+    if from_step > to_step {
+        return None;
+    }
+
+    Some(QueueIndexRange::restore(from_step, to_step))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::QueueWithIntervals;
+
+    #[test]
+    fn test_partition_by_ranges() {
+        let queue: QueueWithIntervals = "1-10,20-25,30".parse().unwrap();
+
+        let shards = queue.partition_by_ranges(&[5, 20, 20, 100]);
+        let shards: Vec<String> = shards.iter().map(|itm| itm.to_string()).collect();
+        assert_eq!(shards, vec!["1-4", "5-10", "", "20-25,30", ""]);
+
+        let shards = queue.partition_by_ranges(&[]);
+        assert_eq!(shards.len(), 1);
+        assert_eq!(shards[0], queue);
+
+        let shards = QueueWithIntervals::<i64>::new().partition_by_ranges(&[0]);
+        assert!(shards.iter().all(|itm| itm.is_empty()));
+    }
+
+    #[test]
+    #[should_panic(expected = "must be sorted")]
+    fn test_partition_by_unsorted_ranges() {
+        let queue: QueueWithIntervals = "1-10".parse().unwrap();
+        queue.partition_by_ranges(&[5, 2]);
+    }
+
+    #[test]
+    fn test_partition_mod() {
+        let queue: QueueWithIntervals = "1-10,12,20-21".parse().unwrap();
+        let shards = queue.partition_mod(3);
+
+        assert_eq!(shards.len(), 3);
+
+        for (remainder, shard) in shards.iter().enumerate() {
+            assert_eq!(shard.remainder(), remainder);
+            assert_eq!(shard.modulus(), 3);
+
+            let expected: Vec<i64> = queue
+                .iter()
+                .filter(|value| value.rem_euclid(3) as usize == remainder)
+                .collect();
+
+            assert_eq!(shard.iter().collect::<Vec<_>>(), expected);
+            assert_eq!(shard.len(), expected.len());
+            assert!(expected.iter().all(|value| shard.contains(*value)));
+        }
+
+        assert_eq!(shards[0].to_queue().to_string(), "3,6,9,12,21");
+        assert!(!shards[0].contains(4));
+        assert!(!shards[0].contains(15));
+
+        let shards = queue.partition_mod(1);
+        assert_eq!(shards[0].to_queue(), queue);
+    }
+
+    #[test]
+    fn test_partition_mod_keeps_compression() {
+        let queue = QueueWithIntervals::<i64>::from_single_interval(-1_000_000_000, 1_000_000_000);
+        let shards = queue.partition_mod(4);
+
+        for shard in &shards {
+            assert_eq!(shard.steps.intervals_iter().count(), 1);
+        }
+
+        assert_eq!(
+            shards.iter().map(|itm| itm.len()).sum::<usize>(),
+            queue.len()
+        );
+        assert_eq!(shards[3].iter().next(), Some(-999_999_997));
+        assert!(shards[2].contains(-2));
+        assert!(shards[0].contains(1_000_000_000));
+        assert!(!shards[2].contains(1_000_000_000));
+
+        let queue = QueueWithIntervals::<i8>::from_single_interval(i8::MIN, i8::MAX);
+        let shards = queue.partition_mod(3);
+        assert_eq!(shards[1].iter().next(), Some(-128));
+        assert_eq!(shards[1].iter().last(), Some(127));
+        assert_eq!(shards.iter().map(|itm| itm.len()).sum::<usize>(), 256);
+
+        let queue = QueueWithIntervals::<u128>::from_single_interval(0, u128::MAX);
+        let shards = queue.partition_mod(2);
+        assert_eq!(shards[1].iter().next(), Some(1));
+        assert!(shards[1].contains(u128::MAX));
+        assert!(!shards[0].contains(u128::MAX));
+    }
+}
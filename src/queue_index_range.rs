@@ -1,3 +1,7 @@
+use std::ops::{
+    Bound, Range, RangeBounds, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive,
+};
+
 use crate::QueueValue;
 
 #[derive(Debug, PartialEq)]
@@ -41,6 +45,14 @@ impl<T: QueueValue> QueueIndexRange<T> {
         }
     }
 
+    /// Range covering every value of the type
+    pub fn new_full() -> QueueIndexRange<T> {
+        QueueIndexRange {
+            from_id: T::min_value(),
+            to_id: T::max_value(),
+        }
+    }
+
     pub fn new_with_single_value(value: T) -> QueueIndexRange<T> {
         QueueIndexRange {
             from_id: value,
@@ -48,6 +60,33 @@ impl<T: QueueValue> QueueIndexRange<T> {
         }
     }
 
+    /// Range covering the bounds. Unbounded ends resolve to the min and max values of the type
+    pub fn from_bounds(bounds: impl RangeBounds<T>) -> QueueIndexRange<T> {
+        let from_id = match bounds.start_bound() {
+            Bound::Included(value) => *value,
+            Bound::Excluded(value) => match value.checked_add_one() {
+                Some(value) => value,
+                None => return QueueIndexRange::new_empty(*value),
+            },
+            Bound::Unbounded => T::min_value(),
+        };
+
+        let to_id = match bounds.end_bound() {
+            Bound::Included(value) => *value,
+            Bound::Excluded(value) => match value.checked_sub_one() {
+                Some(value) => value,
+                None => return QueueIndexRange::new_empty(from_id),
+            },
+            Bound::Unbounded => T::max_value(),
+        };
+
+        if from_id > to_id {
+            return QueueIndexRange::new_empty(from_id);
+        }
+
+        QueueIndexRange { from_id, to_id }
+    }
+
     /*
     pub fn try_join_with_the_next_one(&mut self, next_one: &QueueIndexRange) -> bool {
         if self.to_id + 1 == next_one.from_id {
@@ -64,10 +103,10 @@ impl<T: QueueValue> QueueIndexRange<T> {
         id >= self.from_id && id <= self.to_id
     }
     pub fn is_in_my_interval_to_enqueue(&self, id: T) -> bool {
-        if let Some(from_minus_one) = self.from_id.checked_sub_one() {
-            if let Some(to_plus_one) = self.to_id.checked_add_one() {
-                return id >= from_minus_one && id <= to_plus_one;
-            }
+        if let Some(from_minus_one) = self.from_id.checked_sub_one()
+            && let Some(to_plus_one) = self.to_id.checked_add_one()
+        {
+            return id >= from_minus_one && id <= to_plus_one;
         }
         // Handle edge cases for unsigned types
         if let Some(to_plus_one) = self.to_id.checked_add_one() {
@@ -148,7 +187,7 @@ impl<T: QueueValue> QueueIndexRange<T> {
         }
 
         let result = self.from_id;
-        self.from_id = self.from_id + T::one();
+        self.from_id += T::one();
         Some(result)
     }
 
@@ -157,7 +196,7 @@ impl<T: QueueValue> QueueIndexRange<T> {
             return None;
         }
 
-        return Some(self.from_id);
+        Some(self.from_id)
     }
 
     pub fn enqueue(&mut self, id: T) {
@@ -172,17 +211,17 @@ impl<T: QueueValue> QueueIndexRange<T> {
                 "Warning.... Something went wrong. We are enqueueing the Value {} which is already in the queue. Range: {:?}. ",
                 id, self,
             );
-        } else if let Some(to_plus_one) = self.to_id.checked_add_one() {
-            if to_plus_one == id {
-                self.to_id = id;
-                return;
-            }
+        } else if let Some(to_plus_one) = self.to_id.checked_add_one()
+            && to_plus_one == id
+        {
+            self.to_id = id;
+            return;
         }
-        if let Some(from_minus_one) = self.from_id.checked_sub_one() {
-            if from_minus_one == id {
-                self.from_id = id;
-                return;
-            }
+        if let Some(from_minus_one) = self.from_id.checked_sub_one()
+            && from_minus_one == id
+        {
+            self.from_id = id;
+            return;
         }
         {
             panic!(
@@ -196,13 +235,13 @@ impl<T: QueueValue> QueueIndexRange<T> {
         &self,
         next_item: &QueueIndexRange<T>,
     ) -> Option<QueueIndexRange<T>> {
-        if let Some(to_plus_one) = self.to_id.checked_add_one() {
-            if to_plus_one == next_item.from_id {
-                return Some(QueueIndexRange {
-                    from_id: self.from_id,
-                    to_id: next_item.to_id,
-                });
-            }
+        if let Some(to_plus_one) = self.to_id.checked_add_one()
+            && to_plus_one == next_item.from_id
+        {
+            return Some(QueueIndexRange {
+                from_id: self.from_id,
+                to_id: next_item.to_id,
+            });
         }
 
         None
@@ -215,21 +254,21 @@ impl<T: QueueValue> QueueIndexRange<T> {
             return true;
         }
 
-        if let Some(from_minus_one) = self.from_id.checked_sub_one() {
-            if id_to_join == from_minus_one {
-                self.from_id = id_to_join;
-                return true;
-            }
+        if let Some(from_minus_one) = self.from_id.checked_sub_one()
+            && id_to_join == from_minus_one
+        {
+            self.from_id = id_to_join;
+            return true;
         }
 
-        if let Some(to_plus_one) = self.to_id.checked_add_one() {
-            if id_to_join == to_plus_one {
-                self.to_id = id_to_join;
-                return true;
-            }
+        if let Some(to_plus_one) = self.to_id.checked_add_one()
+            && id_to_join == to_plus_one
+        {
+            self.to_id = id_to_join;
+            return true;
         }
 
-        return false;
+        false
     }
 
     pub fn is_empty(&self) -> bool {
@@ -273,45 +312,42 @@ impl<T: QueueValue> QueueIndexRange<T> {
             return Some(QueueIndexRangeCompare::Above);
         }
 
-        return Some(QueueIndexRangeCompare::Inside);
+        Some(QueueIndexRangeCompare::Inside)
+    }
+
+    /// Returns the part of the range which is within [from_id, to_id]. The result may be empty
+    pub fn clip(&self, from_id: T, to_id: T) -> QueueIndexRange<T> {
+        QueueIndexRange {
+            from_id: self.from_id.max(from_id),
+            to_id: self.to_id.min(to_id),
+        }
     }
 
     pub fn covered_with_range_to_insert(&self, range_to_insert: &QueueIndexRange<T>) -> bool {
         range_to_insert.from_id <= self.from_id && range_to_insert.to_id >= self.to_id
     }
 
-    #[cfg(test)]
-    pub fn to_string(&self) -> String {
+    pub fn len(&self) -> usize {
+        usize::try_from(self.len_u128()).unwrap_or(usize::MAX)
+    }
+
+    /// Returns the number of values in the range.
+    /// A range covering every i128 or u128 value holds 2^128 values and saturates to u128::MAX
+    pub fn len_u128(&self) -> u128 {
         if self.is_empty() {
-            return "EMPTY".to_string();
+            return 0;
         }
 
-        return format!("{:?} - {:?}", self.from_id, self.to_id);
+        T::distance(self.from_id, self.to_id).saturating_add(1)
     }
 
-    pub fn len(&self) -> usize {
-        if self.from_id > self.to_id {
+    // Number of values modulo 2^128, so totals can be added and subtracted without saturating
+    pub(crate) fn values_count(&self) -> u128 {
+        if self.is_empty() {
             return 0;
         }
-        // Calculate length safely
-        // For most practical ranges, this will work fine
-        // For very large ranges, we use a loop to avoid overflow
-        let mut count = 0usize;
-        let mut current = self.from_id;
-        while current <= self.to_id {
-            count = count.saturating_add(1);
-            if let Some(next) = current.checked_add_one() {
-                if next > self.to_id {
-                    break;
-                }
-                current = next;
-            } else {
-                // Reached max value
-                count = count.saturating_add(1);
-                break;
-            }
-        }
-        count
+
+        T::distance(self.from_id, self.to_id).wrapping_add(1)
     }
 
     /// Returns the length as the same type T
@@ -327,6 +363,54 @@ impl<T: QueueValue> QueueIndexRange<T> {
     }
 }
 
+impl<T: QueueValue> From<&QueueIndexRange<T>> for QueueIndexRange<T> {
+    fn from(range: &QueueIndexRange<T>) -> Self {
+        range.clone()
+    }
+}
+
+impl<T: QueueValue> From<RangeInclusive<T>> for QueueIndexRange<T> {
+    fn from(range: RangeInclusive<T>) -> Self {
+        QueueIndexRange::from_bounds(range)
+    }
+}
+
+impl<T: QueueValue> From<Range<T>> for QueueIndexRange<T> {
+    fn from(range: Range<T>) -> Self {
+        QueueIndexRange::from_bounds(range)
+    }
+}
+
+impl<T: QueueValue> From<RangeFrom<T>> for QueueIndexRange<T> {
+    fn from(range: RangeFrom<T>) -> Self {
+        QueueIndexRange::from_bounds(range)
+    }
+}
+
+impl<T: QueueValue> From<RangeTo<T>> for QueueIndexRange<T> {
+    fn from(range: RangeTo<T>) -> Self {
+        QueueIndexRange::from_bounds(range)
+    }
+}
+
+impl<T: QueueValue> From<RangeToInclusive<T>> for QueueIndexRange<T> {
+    fn from(range: RangeToInclusive<T>) -> Self {
+        QueueIndexRange::from_bounds(range)
+    }
+}
+
+impl<T: QueueValue> From<RangeFull> for QueueIndexRange<T> {
+    fn from(_: RangeFull) -> Self {
+        QueueIndexRange::new_full()
+    }
+}
+
+impl<T: QueueValue> From<(Bound<T>, Bound<T>)> for QueueIndexRange<T> {
+    fn from(bounds: (Bound<T>, Bound<T>)) -> Self {
+        QueueIndexRange::from_bounds(bounds)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -339,7 +423,7 @@ mod tests {
         assert_eq!(0, index_range.from_id);
         assert_eq!(-1, index_range.to_id);
 
-        println!("{}", index_range.to_string());
+        println!("{}", index_range);
     }
 
     #[test]
@@ -374,7 +458,7 @@ mod tests {
         assert_eq!(6, res.unwrap());
 
         let res = index_range.dequeue();
-        assert_eq!(true, res.is_none());
+        assert!(res.is_none());
     }
 
     #[test]
@@ -382,16 +466,16 @@ mod tests {
         let index_range = QueueIndexRange::restore(5, 10);
 
         let _result = index_range.compare_with(4).unwrap();
-        assert_eq!(true, matches!(QueueIndexRangeCompare::Below, _result));
+        assert!(matches!(QueueIndexRangeCompare::Below, _result));
 
         let _result = index_range.compare_with(5).unwrap();
-        assert_eq!(true, matches!(QueueIndexRangeCompare::Inside, _result));
+        assert!(matches!(QueueIndexRangeCompare::Inside, _result));
 
         let _result = index_range.compare_with(10).unwrap();
-        assert_eq!(true, matches!(QueueIndexRangeCompare::Inside, _result));
+        assert!(matches!(QueueIndexRangeCompare::Inside, _result));
 
         let _result = index_range.compare_with(11).unwrap();
-        assert_eq!(true, matches!(QueueIndexRangeCompare::Above, _result));
+        assert!(matches!(QueueIndexRangeCompare::Above, _result));
     }
 
     #[test]
@@ -555,6 +639,22 @@ mod tests {
         assert!(!range.covered_with_range_to_insert(&QueueIndexRange::restore(5, 15)));
     }
 
+    #[test]
+    fn test_clip() {
+        let range = QueueIndexRange::restore(10, 20);
+
+        let clipped = range.clip(15, 30);
+        assert_eq!(15, clipped.from_id);
+        assert_eq!(20, clipped.to_id);
+
+        let clipped = range.clip(0, 12);
+        assert_eq!(10, clipped.from_id);
+        assert_eq!(12, clipped.to_id);
+
+        assert!(range.clip(21, 30).is_empty());
+        assert!(range.clip(0, 9).is_empty());
+    }
+
     #[test]
     fn test_len_calculations() {
         let range1 = QueueIndexRange::restore(10, 10);
@@ -570,6 +670,28 @@ mod tests {
         assert_eq!(0, range4.len());
     }
 
+    #[test]
+    fn test_len_of_huge_ranges() {
+        let range = QueueIndexRange::restore(0u64, 10_000_000_000);
+        assert_eq!(10_000_000_001, range.len());
+
+        let range = QueueIndexRange::restore(i64::MIN, i64::MAX);
+        assert_eq!(1u128 << 64, range.len_u128());
+
+        let range = QueueIndexRange::restore(i8::MIN, i8::MAX);
+        assert_eq!(256, range.len());
+
+        let range = QueueIndexRange::restore(u128::MAX - 1, u128::MAX);
+        assert_eq!(2, range.len_u128());
+
+        let range = QueueIndexRange::restore(i128::MIN, i128::MAX);
+        assert_eq!(u128::MAX, range.len_u128());
+        assert_eq!(usize::MAX, range.len());
+
+        let range = QueueIndexRange::restore(u128::MIN, u128::MAX);
+        assert_eq!(u128::MAX, range.len_u128());
+    }
+
     #[test]
     fn test_iterator_for_range() {
         let range = QueueIndexRange::restore(10, 12);
@@ -597,4 +719,29 @@ mod tests {
         let collected: Vec<i64> = range.into_iter().collect();
         assert_eq!(vec![-5, -4, -3, -2], collected);
     }
+
+    #[test]
+    fn test_from_std_ranges() {
+        fn pair(range: QueueIndexRange<u8>) -> (u8, u8) {
+            (range.from_id, range.to_id)
+        }
+
+        assert_eq!(pair((10..=20).into()), (10, 20));
+        assert_eq!(pair((10..20).into()), (10, 19));
+        assert_eq!(pair((10..).into()), (10, u8::MAX));
+        assert_eq!(pair((..20).into()), (0, 19));
+        assert_eq!(pair((..=20).into()), (0, 20));
+        assert_eq!(pair((..).into()), (0, u8::MAX));
+        assert_eq!(
+            pair((Bound::Excluded(10), Bound::Unbounded).into()),
+            (11, u8::MAX)
+        );
+
+        assert!(QueueIndexRange::from(10u8..10).is_empty());
+        assert!(QueueIndexRange::from(0u8..0).is_empty());
+        let (from, to) = (20u8, 10u8);
+        assert!(QueueIndexRange::from(from..=to).is_empty());
+        assert!(QueueIndexRange::from((Bound::Excluded(u8::MAX), Bound::Unbounded)).is_empty());
+        assert!(QueueIndexRange::<i8>::from(..i8::MIN).is_empty());
+    }
 }
@@ -23,6 +23,12 @@ pub trait QueueValue:
     /// Returns the value representing one
     fn one() -> Self;
 
+    /// Returns the smallest value of the type
+    fn min_value() -> Self;
+
+    /// Returns the largest value of the type
+    fn max_value() -> Self;
+
     /// Safely subtracts one, handling underflow for unsigned types.
     /// Returns None if the operation would underflow.
     fn checked_sub_one(self) -> Option<Self>;
@@ -30,10 +36,17 @@ pub trait QueueValue:
     /// Safely adds one, handling overflow.
     /// Returns None if the operation would overflow.
     fn checked_add_one(self) -> Option<Self>;
+
+    /// Returns `to - from` widened to u128, so it never overflows for any integer type.
+    /// `from` must not be greater than `to`.
+    fn distance(from: Self, to: Self) -> u128;
+
+    /// Returns `self + distance`. The result must fit into the type.
+    fn add_distance(self, distance: u128) -> Self;
 }
 
 macro_rules! impl_queue_value {
-    ($($t:ty),*) => {
+    ($($t:ty => $unsigned:ty),*) => {
         $(
             impl QueueValue for $t {
                 fn zero() -> Self {
@@ -44,6 +57,14 @@ macro_rules! impl_queue_value {
                     1
                 }
 
+                fn min_value() -> Self {
+                    <$t>::MIN
+                }
+
+                fn max_value() -> Self {
+                    <$t>::MAX
+                }
+
                 fn checked_sub_one(self) -> Option<Self> {
                     self.checked_sub(1)
                 }
@@ -51,6 +72,15 @@ macro_rules! impl_queue_value {
                 fn checked_add_one(self) -> Option<Self> {
                     self.checked_add(1)
                 }
+
+                fn distance(from: Self, to: Self) -> u128 {
+                    // The difference always fits into the unsigned type of the same width
+                    to.wrapping_sub(from) as $unsigned as u128
+                }
+
+                fn add_distance(self, distance: u128) -> Self {
+                    (self as $unsigned).wrapping_add(distance as $unsigned) as $t
+                }
             }
         )*
     };
@@ -60,5 +90,16 @@ macro_rules! impl_queue_value {
 // Signed integers: i8, i16, i32, i64, i128, isize
 // Unsigned integers: u8, u16, u32, u64, u128, usize
 impl_queue_value!(
-    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize
+    i8 => u8,
+    i16 => u16,
+    i32 => u32,
+    i64 => u64,
+    i128 => u128,
+    isize => usize,
+    u8 => u8,
+    u16 => u16,
+    u32 => u32,
+    u64 => u64,
+    u128 => u128,
+    usize => usize
 );
@@ -1,6 +1,18 @@
-use crate::{QueueIndexRange, QueueValue};
-
-use super::{iterator::QueueWithIntervalsIterator, *};
+use std::{
+    ops::{Bound, RangeBounds},
+    sync::OnceLock,
+};
+
+use crate::{InvariantViolation, QueueIndexRange, QueueValue};
+
+use super::{
+    invariants::check_intervals,
+    iterator::{
+        QueueIndexRangesIterator, QueueWithIntervalsIterator, QueueWithIntervalsRefIterator,
+    },
+    set_operations::push_interval,
+    *,
+};
 
 //Illustrations are https://docs.google.com/spreadsheets/d/1oRFoiUkPm3h8Tz3BSVNCSBG3_pM84MlZLpJDCAPGKLs/edit?gid=0#gid=0
 
@@ -9,11 +21,21 @@ pub enum QueueWithIntervalsError {
     MessagesNotFound,
     QueueIsEmpty,
     MessageExists,
+    // from_id of the interval is the minimum value of the type and can not be decremented
+    FromIdUnderflow,
+    // to_id of the interval is the maximum value of the type and can not be incremented
+    ToIdOverflow,
+    // Intervals are not sorted, overlap or are adjacent
+    InconsistentIntervals,
 }
 
 #[derive(Debug, Clone)]
 pub struct QueueWithIntervals<T: QueueValue = i64> {
     pub(crate) intervals: Vec<QueueIndexRange<T>>,
+    // Amount of values modulo 2^128. Zero with non-empty intervals means every i128/u128 value is in the queue
+    pub(crate) values_count: u128,
+    // Lazily built amounts of values before each interval. Dropped on every mutation
+    pub(crate) prefix_counts: OnceLock<Vec<u128>>,
 }
 
 impl<T: QueueValue> Default for QueueWithIntervals<T> {
@@ -25,13 +47,13 @@ impl<T: QueueValue> QueueWithIntervals<T> {
     pub fn new() -> QueueWithIntervals<T> {
         Self {
             intervals: vec![QueueIndexRange::new_empty(T::zero())],
+            values_count: 0,
+            prefix_counts: OnceLock::new(),
         }
     }
 
     pub fn merge(&mut self, other: Self) {
-        for other_interval in other.intervals.into_iter().rev() {
-            self.enqueue_range(other_interval);
-        }
+        self.union_with(&other);
     }
 
     pub fn get_interval(&self, index: usize) -> Option<&QueueIndexRange<T>> {
@@ -43,18 +65,59 @@ impl<T: QueueValue> QueueWithIntervals<T> {
     }
 
     pub fn restore(mut intervals: Vec<QueueIndexRange<T>>) -> Self {
-        if intervals.len() == 0 {
-            return Self {
-                intervals: vec![QueueIndexRange::new_empty(T::zero())],
-            };
+        if intervals.is_empty() {
+            return Self::new();
         }
         intervals.sort_by_key(|itm| itm.from_id);
-        Self { intervals }
+        let values_count = Self::count_values(&intervals);
+        Self {
+            intervals,
+            values_count,
+            prefix_counts: OnceLock::new(),
+        }
+    }
+
+    /// Restores the queue from intervals which must be sorted, not empty, not overlapping and not adjacent.
+    /// A single empty interval restores an empty queue
+    pub fn try_restore(intervals: Vec<QueueIndexRange<T>>) -> Result<Self, InvariantViolation<T>> {
+        if intervals.is_empty() {
+            return Ok(Self::new());
+        }
+
+        if intervals.len() == 1 && intervals[0].is_empty() {
+            return Ok(Self {
+                intervals,
+                values_count: 0,
+                prefix_counts: OnceLock::new(),
+            });
+        }
+
+        check_intervals(&intervals)?;
+
+        Ok(Self::from_sorted_intervals(intervals))
+    }
+
+    /// Restores the queue from intervals in any order. Empty intervals are dropped,
+    /// overlapping and adjacent intervals are joined
+    pub fn restore_normalized(mut intervals: Vec<QueueIndexRange<T>>) -> Self {
+        intervals.retain(|itm| !itm.is_empty());
+        intervals.sort_by_key(|itm| itm.from_id);
+
+        let mut result = Vec::with_capacity(intervals.len());
+
+        for interval in &intervals {
+            push_interval(&mut result, interval);
+        }
+
+        Self::from_sorted_intervals(result)
     }
 
     pub fn from_single_interval(from_id: T, to_id: T) -> Self {
+        let interval = QueueIndexRange { from_id, to_id };
         Self {
-            intervals: vec![QueueIndexRange { from_id, to_id }],
+            values_count: interval.values_count(),
+            intervals: vec![interval],
+            prefix_counts: OnceLock::new(),
         }
     }
 
@@ -65,7 +128,9 @@ impl<T: QueueValue> QueueWithIntervals<T> {
         }
 
         intervals.sort_by_key(|itm| itm.from_id);
+        self.values_count = Self::count_values(&intervals);
         self.intervals = intervals;
+        self.after_mutation("reset");
     }
 
     pub fn clean(&mut self) {
@@ -76,11 +141,14 @@ impl<T: QueueValue> QueueWithIntervals<T> {
 
         first.to_id = to_id;
         first.make_empty();
+
+        self.values_count = 0;
+        self.after_mutation("clean");
     }
 
     pub fn is_empty(&self) -> bool {
         if self.intervals.len() == 1 {
-            return self.intervals.get(0).unwrap().is_empty();
+            return self.intervals.first().unwrap().is_empty();
         }
 
         false
@@ -123,6 +191,9 @@ impl<T: QueueValue> QueueWithIntervals<T> {
             IndexToRemoveValue::NoValue => return Err(QueueWithIntervalsError::MessagesNotFound),
         }
 
+        self.values_count = self.values_count.wrapping_sub(1);
+        self.after_mutation("remove");
+
         Ok(())
     }
 
@@ -139,13 +210,33 @@ impl<T: QueueValue> QueueWithIntervals<T> {
         }
     }
 
+    // Removes the first intervals. The last interval is kept as the empty one when all of them go
+    pub(crate) fn remove_head_intervals(&mut self, amount: usize) {
+        if amount < self.intervals.len() {
+            self.intervals.drain(..amount);
+            return;
+        }
+
+        self.intervals.drain(..self.intervals.len() - 1);
+        self.intervals[0].make_empty();
+    }
+
     pub fn enqueue(&mut self, value: T) {
-        if let Some(first) = self.intervals.first_mut() {
-            if first.is_empty() {
-                first.from_id = value;
-                first.to_id = value;
-                return;
-            }
+        if let Err(err) = self.try_enqueue(value) {
+            panic!("Can not enqueue value {}: {:?}", value, err);
+        }
+    }
+
+    /// Enqueues the value. Returns false if the value is already in the queue
+    pub fn try_enqueue(&mut self, value: T) -> Result<bool, QueueWithIntervalsError> {
+        if let Some(first) = self.intervals.first_mut()
+            && first.is_empty()
+        {
+            first.from_id = value;
+            first.to_id = value;
+            self.values_count = 1;
+            self.after_mutation("enqueue");
+            return Ok(true);
         }
 
         match IndexToInsertValue::new(&self.intervals, value) {
@@ -159,7 +250,7 @@ impl<T: QueueValue> QueueWithIntervals<T> {
                 {
                     self.intervals.get_mut(index).unwrap().from_id = prev;
                 } else {
-                    panic!("Cannot decrement from_id below zero");
+                    return Err(QueueWithIntervalsError::FromIdUnderflow);
                 }
             }
             IndexToInsertValue::MergeToRight(index) => {
@@ -172,7 +263,7 @@ impl<T: QueueValue> QueueWithIntervals<T> {
                 {
                     self.intervals.get_mut(index).unwrap().to_id = next;
                 } else {
-                    panic!("Cannot increment to_id beyond max value");
+                    return Err(QueueWithIntervalsError::ToIdOverflow);
                 }
             }
             IndexToInsertValue::InsertAsNewInterval(index) => {
@@ -185,14 +276,19 @@ impl<T: QueueValue> QueueWithIntervals<T> {
                 );
             }
             IndexToInsertValue::MergeTwoIntervals(index) => {
-                let value = self.intervals.remove(index + 1);
-                if self.intervals.len() == 0 {
-                    panic!("Somehow intervals got empty");
+                if index + 1 >= self.intervals.len() {
+                    return Err(QueueWithIntervalsError::InconsistentIntervals);
                 }
+                let value = self.intervals.remove(index + 1);
                 self.intervals.get_mut(index).unwrap().to_id = value.to_id;
             }
-            IndexToInsertValue::HasValue => {}
+            IndexToInsertValue::HasValue => return Ok(false),
         }
+
+        self.values_count = self.values_count.wrapping_add(1);
+        self.after_mutation("enqueue");
+
+        Ok(true)
     }
 
     /*
@@ -229,11 +325,14 @@ impl<T: QueueValue> QueueWithIntervals<T> {
             self.remove_interval(0);
         }
 
+        self.values_count = self.values_count.wrapping_sub(1);
+        self.after_mutation("dequeue");
+
         Some(result)
     }
 
     pub fn peek(&self) -> Option<T> {
-        let result = self.intervals.get(0).unwrap();
+        let result = self.intervals.first().unwrap();
 
         if result.is_empty() {
             return None;
@@ -242,6 +341,41 @@ impl<T: QueueValue> QueueWithIntervals<T> {
         Some(result.from_id)
     }
 
+    /// Dequeues the biggest value
+    pub fn dequeue_back(&mut self) -> Option<T> {
+        let last_index = self.intervals.len() - 1;
+
+        let (result, is_empty) = {
+            let itm = self.intervals.get_mut(last_index).unwrap();
+            if itm.is_empty() {
+                return None;
+            }
+
+            let result = itm.to_id;
+            if itm.from_id == itm.to_id {
+                itm.make_empty();
+            } else {
+                itm.to_id = itm.to_id.checked_sub_one().unwrap();
+            }
+
+            (result, itm.is_empty())
+        };
+
+        if is_empty {
+            self.remove_interval(last_index);
+        }
+
+        self.values_count = self.values_count.wrapping_sub(1);
+        self.after_mutation("dequeue_back");
+
+        Some(result)
+    }
+
+    /// Returns the biggest value without removing it
+    pub fn peek_back(&self) -> Option<T> {
+        self.get_max_id()
+    }
+
     pub fn get_snapshot(&self) -> Vec<QueueIndexRange<T>> {
         if self.is_empty() {
             return vec![];
@@ -253,7 +387,7 @@ impl<T: QueueValue> QueueWithIntervals<T> {
     // Returns non - only if we did not put any messages into the queue never
 
     pub fn get_min_id(&self) -> Option<T> {
-        let first = self.intervals.get(0).unwrap();
+        let first = self.intervals.first().unwrap();
 
         if first.is_empty() {
             return None;
@@ -263,7 +397,7 @@ impl<T: QueueValue> QueueWithIntervals<T> {
     }
 
     pub fn get_max_id(&self) -> Option<T> {
-        let last = self.intervals.get(self.intervals.len() - 1).unwrap();
+        let last = self.intervals.last().unwrap();
         if last.is_empty() {
             return None;
         }
@@ -272,36 +406,221 @@ impl<T: QueueValue> QueueWithIntervals<T> {
     }
 
     pub fn has_message(&self, id: T) -> bool {
-        for interval in &self.intervals {
-            if interval.is_in_my_interval(id) {
-                return true;
+        let index = self.intervals.partition_point(|itm| itm.to_id < id);
+
+        match self.intervals.get(index) {
+            Some(interval) => interval.is_in_my_interval(id),
+            None => false,
+        }
+    }
+
+    /// Checks if every value of the range is in the queue. An empty range is always contained
+    pub fn contains_range(&self, range: &QueueIndexRange<T>) -> bool {
+        if range.is_empty() {
+            return true;
+        }
+
+        // Intervals are not adjacent, so the whole range must fit into a single interval
+        let index = self
+            .intervals
+            .partition_point(|itm| itm.to_id < range.from_id);
+
+        match self.intervals.get(index) {
+            Some(interval) => {
+                !interval.is_empty()
+                    && interval.from_id <= range.from_id
+                    && range.to_id <= interval.to_id
             }
+            None => false,
         }
-        false
+    }
+
+    /// Checks if at least one value of the range is in the queue
+    pub fn intersects_range(&self, range: &QueueIndexRange<T>) -> bool {
+        if range.is_empty() {
+            return false;
+        }
+
+        let index = self
+            .intervals
+            .partition_point(|itm| itm.to_id < range.from_id);
+
+        match self.intervals.get(index) {
+            Some(interval) => !interval.is_empty() && interval.from_id <= range.to_id,
+            None => false,
+        }
+    }
+
+    /// Returns the amount of values which are within the bounds, saturating at usize::MAX
+    pub fn count_in_range(&self, bounds: impl RangeBounds<T>) -> usize {
+        let Some((_, from_id, to_id)) = self.find_intervals_in_bounds(&bounds) else {
+            return 0;
+        };
+
+        // Bounds overlap the queue, so there are values within them
+        saturate_count(
+            self.count_values_in_range(&QueueIndexRange::restore(from_id, to_id)),
+            true,
+        )
     }
 
     pub fn queue_size(&self) -> usize {
-        let mut result = 0;
+        self.len()
+    }
+
+    pub fn iter(&self) -> QueueWithIntervalsRefIterator<'_, T> {
+        QueueWithIntervalsRefIterator::new(self.get_non_empty_intervals(), self.values_count)
+    }
+
+    /// Iterates the stored intervals
+    pub fn intervals_iter(&self) -> std::slice::Iter<'_, QueueIndexRange<T>> {
+        self.get_non_empty_intervals().iter()
+    }
+
+    // Intervals without the empty placeholder of an empty queue
+    pub(crate) fn get_non_empty_intervals(&self) -> &[QueueIndexRange<T>] {
+        if self.is_empty() {
+            return &[];
+        }
+
+        &self.intervals
+    }
+
+    // Intervals must be sorted, non-overlapping and not adjacent
+    pub(crate) fn from_sorted_intervals(intervals: Vec<QueueIndexRange<T>>) -> Self {
+        let mut result = Self::new();
+        result.set_sorted_intervals(intervals);
+        result
+    }
 
-        for interval in &self.intervals {
-            result += interval.len()
+    // Intervals must be sorted, non-overlapping and not adjacent
+    pub(crate) fn set_sorted_intervals(&mut self, intervals: Vec<QueueIndexRange<T>>) {
+        if intervals.is_empty() {
+            self.clean();
+            return;
         }
 
-        result as usize
+        self.values_count = Self::count_values(&intervals);
+        self.intervals = intervals;
+        self.after_mutation("set_sorted_intervals");
     }
 
-    pub fn iter(&self) -> QueueWithIntervalsIterator<T> {
-        QueueWithIntervalsIterator::new(self.clone())
+    // Every mutation goes through here: drops the prefix counts of nth/rank and validates the queue
+    // when the check-invariants feature is enabled
+    pub(crate) fn after_mutation(&mut self, operation: &str) {
+        self.prefix_counts.take();
+        self.check_invariants(operation);
+    }
+
+    /// Iterates values which are within the bounds
+    pub fn range(&self, bounds: impl RangeBounds<T>) -> QueueWithIntervalsRefIterator<'_, T> {
+        match self.find_intervals_in_bounds(&bounds) {
+            Some((intervals, from_id, to_id)) => {
+                QueueWithIntervalsRefIterator::new_in_bounds(intervals, from_id, to_id)
+            }
+            None => QueueWithIntervalsRefIterator::new(&[], 0),
+        }
+    }
+
+    /// Iterates intervals clipped to the bounds
+    pub fn range_intervals(&self, bounds: impl RangeBounds<T>) -> QueueIndexRangesIterator<'_, T> {
+        match self.find_intervals_in_bounds(&bounds) {
+            Some((intervals, from_id, to_id)) => {
+                QueueIndexRangesIterator::new(intervals, from_id, to_id)
+            }
+            None => QueueIndexRangesIterator::new(&[], T::zero(), T::zero()),
+        }
+    }
+
+    // Returns the intervals overlapping the bounds together with the bounds resolved to inclusive ids
+    pub(crate) fn find_intervals_in_bounds(
+        &self,
+        bounds: &impl RangeBounds<T>,
+    ) -> Option<(&[QueueIndexRange<T>], T, T)> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let from_id = match bounds.start_bound() {
+            Bound::Included(value) => *value,
+            Bound::Excluded(value) => value.checked_add_one()?,
+            Bound::Unbounded => self.intervals.first().unwrap().from_id,
+        };
+
+        let to_id = match bounds.end_bound() {
+            Bound::Included(value) => *value,
+            Bound::Excluded(value) => value.checked_sub_one()?,
+            Bound::Unbounded => self.intervals.last().unwrap().to_id,
+        };
+
+        if from_id > to_id {
+            return None;
+        }
+
+        let start = self.intervals.partition_point(|itm| itm.to_id < from_id);
+        let end = self.intervals.partition_point(|itm| itm.from_id <= to_id);
+
+        if start >= end {
+            return None;
+        }
+
+        Some((&self.intervals[start..end], from_id, to_id))
     }
+
+    /// Returns the amount of values in the queue, saturating at usize::MAX
     pub fn len(&self) -> usize {
-        let mut result = 0usize;
+        saturate_count(self.len_u128(), false)
+    }
 
-        for interval in &self.intervals {
-            result = result.saturating_add(interval.len());
+    /// Returns the amount of values in the queue.
+    /// A queue holding every i128 or u128 value has 2^128 values and saturates to u128::MAX
+    pub fn len_u128(&self) -> u128 {
+        // restore keeps empty intervals, so a queue may be not empty and still hold no values
+        if self.values_count == 0 && self.intervals.iter().any(|itm| !itm.is_empty()) {
+            return u128::MAX;
+        }
+
+        self.values_count
+    }
+
+    // Amount of values of the range which are already in the queue, modulo 2^128
+    pub(crate) fn count_values_in_range(&self, range: &QueueIndexRange<T>) -> u128 {
+        if range.is_empty() {
+            return 0;
+        }
+
+        let start = self
+            .intervals
+            .partition_point(|itm| itm.to_id < range.from_id);
+
+        let mut result = 0u128;
+
+        for interval in &self.intervals[start..] {
+            if interval.from_id > range.to_id {
+                break;
+            }
+
+            result = result.wrapping_add(interval.clip(range.from_id, range.to_id).values_count());
         }
 
         result
     }
+
+    fn count_values(intervals: &[QueueIndexRange<T>]) -> u128 {
+        intervals
+            .iter()
+            .fold(0u128, |acc, itm| acc.wrapping_add(itm.values_count()))
+    }
+}
+
+// Counts are kept modulo 2^128, so a zero count of values which are there means all 2^128 i128/u128 values.
+// Converts such a count to usize, saturating 2^128 and everything above usize::MAX at usize::MAX
+pub(crate) fn saturate_count(count: u128, has_values: bool) -> usize {
+    if count == 0 && has_values {
+        return usize::MAX;
+    }
+
+    usize::try_from(count).unwrap_or(usize::MAX)
 }
 
 impl<T: QueueValue> IntoIterator for QueueWithIntervals<T> {
@@ -310,17 +629,17 @@ impl<T: QueueValue> IntoIterator for QueueWithIntervals<T> {
     type IntoIter = QueueWithIntervalsIterator<T>;
 
     fn into_iter(self) -> QueueWithIntervalsIterator<T> {
-        QueueWithIntervalsIterator::new(self.clone())
+        QueueWithIntervalsIterator::new(self)
     }
 }
 
 impl<'s, T: QueueValue> IntoIterator for &'s QueueWithIntervals<T> {
     type Item = T;
 
-    type IntoIter = QueueWithIntervalsIterator<T>;
+    type IntoIter = QueueWithIntervalsRefIterator<'s, T>;
 
-    fn into_iter(self) -> QueueWithIntervalsIterator<T> {
-        QueueWithIntervalsIterator::new(self.clone())
+    fn into_iter(self) -> QueueWithIntervalsRefIterator<'s, T> {
+        self.iter()
     }
 }
 
@@ -332,7 +651,7 @@ mod tests {
     fn test_empty() {
         let queue = QueueWithIntervals::<i64>::new();
 
-        assert_eq!(true, queue.get_min_id().is_none());
+        assert!(queue.get_min_id().is_none());
         assert_eq!(0, queue.queue_size());
     }
 
@@ -373,16 +692,44 @@ mod tests {
 
         assert_eq!(2, queue.queue_size());
 
-        assert_eq!(queue.intervals.get(0).unwrap().from_id, 5);
-        assert_eq!(queue.intervals.get(0).unwrap().to_id, 6);
+        assert_eq!(queue.intervals.first().unwrap().from_id, 5);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 6);
 
         assert_eq!(5, queue.dequeue().unwrap());
-        assert_eq!(queue.intervals.get(0).unwrap().from_id, 6);
-        assert_eq!(queue.intervals.get(0).unwrap().to_id, 6);
+        assert_eq!(queue.intervals.first().unwrap().from_id, 6);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 6);
         assert_eq!(6, queue.dequeue().unwrap());
-        assert!(queue.intervals.get(0).unwrap().is_empty());
+        assert!(queue.intervals.first().unwrap().is_empty());
 
-        assert_eq!(true, queue.dequeue().is_none());
+        assert!(queue.dequeue().is_none());
+    }
+
+    #[test]
+    fn test_dequeue_back() {
+        let mut queue = QueueWithIntervals::new();
+
+        queue.enqueue(5);
+        queue.enqueue(6);
+        queue.enqueue(10);
+
+        assert_eq!(Some(10), queue.peek_back());
+        assert_eq!(10, queue.dequeue_back().unwrap());
+        assert_eq!(1, queue.intervals.len());
+        assert_eq!(Some(6), queue.peek_back());
+
+        assert_eq!(6, queue.dequeue_back().unwrap());
+        assert_eq!(queue.intervals.first().unwrap().to_id, 5);
+        assert_eq!(1, queue.queue_size());
+
+        assert_eq!(5, queue.dequeue_back().unwrap());
+        assert!(queue.intervals.first().unwrap().is_empty());
+        assert!(queue.peek_back().is_none());
+        assert!(queue.dequeue_back().is_none());
+
+        let mut queue = QueueWithIntervals::<u8>::from_single_interval(0, 1);
+        assert_eq!(1, queue.dequeue_back().unwrap());
+        assert_eq!(0, queue.dequeue_back().unwrap());
+        assert!(queue.is_empty());
     }
 
     #[test]
@@ -416,8 +763,8 @@ mod tests {
 
         assert_eq!(1, queue.intervals.len());
 
-        assert_eq!(201, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(204, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(201, queue.intervals.first().unwrap().from_id);
+        assert_eq!(204, queue.intervals.first().unwrap().to_id);
     }
 
     #[test]
@@ -434,8 +781,8 @@ mod tests {
 
         assert_eq!(1, queue.intervals.len());
 
-        assert_eq!(200, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(203, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(200, queue.intervals.first().unwrap().from_id);
+        assert_eq!(203, queue.intervals.first().unwrap().to_id);
     }
 
     #[test]
@@ -452,8 +799,8 @@ mod tests {
 
         assert_eq!(2, queue.intervals.len());
 
-        assert_eq!(200, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(201, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(200, queue.intervals.first().unwrap().from_id);
+        assert_eq!(201, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(203, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(204, queue.intervals.get(1).unwrap().to_id);
@@ -474,8 +821,8 @@ mod tests {
         queue.remove(202).unwrap();
         assert_eq!(2, queue.intervals.len());
 
-        assert_eq!(200, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(201, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(200, queue.intervals.first().unwrap().from_id);
+        assert_eq!(201, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(203, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(206, queue.intervals.get(1).unwrap().to_id);
@@ -483,8 +830,8 @@ mod tests {
         queue.remove(205).unwrap();
         assert_eq!(3, queue.intervals.len());
 
-        assert_eq!(200, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(201, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(200, queue.intervals.first().unwrap().from_id);
+        assert_eq!(201, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(203, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(204, queue.intervals.get(1).unwrap().to_id);
@@ -496,8 +843,8 @@ mod tests {
         queue.remove(204).unwrap();
         assert_eq!(2, queue.intervals.len());
 
-        assert_eq!(200, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(201, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(200, queue.intervals.first().unwrap().from_id);
+        assert_eq!(201, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(206, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(206, queue.intervals.get(1).unwrap().to_id);
@@ -506,20 +853,20 @@ mod tests {
 
         assert_eq!(1, queue.intervals.len());
 
-        assert_eq!(200, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(201, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(200, queue.intervals.first().unwrap().from_id);
+        assert_eq!(201, queue.intervals.first().unwrap().to_id);
 
         queue.remove(201).unwrap();
 
         assert_eq!(1, queue.intervals.len());
 
-        assert_eq!(200, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(200, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(200, queue.intervals.first().unwrap().from_id);
+        assert_eq!(200, queue.intervals.first().unwrap().to_id);
 
         queue.remove(200).unwrap();
 
         assert_eq!(1, queue.intervals.len());
-        assert!(queue.intervals.get(0).unwrap().is_empty());
+        assert!(queue.intervals.first().unwrap().is_empty());
     }
 
     #[test]
@@ -540,8 +887,8 @@ mod tests {
         queue.remove(205).unwrap();
         assert_eq!(3, queue.intervals.len());
 
-        assert_eq!(200, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(201, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(200, queue.intervals.first().unwrap().from_id);
+        assert_eq!(201, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(203, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(204, queue.intervals.get(1).unwrap().to_id);
@@ -552,8 +899,8 @@ mod tests {
         queue.remove(206).unwrap();
         assert_eq!(2, queue.intervals.len());
 
-        assert_eq!(200, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(201, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(200, queue.intervals.first().unwrap().from_id);
+        assert_eq!(201, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(203, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(204, queue.intervals.get(1).unwrap().to_id);
@@ -572,7 +919,7 @@ mod tests {
 
         let result = queue.dequeue();
 
-        assert_eq!(true, result.is_none());
+        assert!(result.is_none());
 
         assert_eq!(0, queue.queue_size());
     }
@@ -588,8 +935,8 @@ mod tests {
         queue.enqueue(508);
         assert_eq!(queue.intervals.len(), 2);
 
-        assert_eq!(502, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(504, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(502, queue.intervals.first().unwrap().from_id);
+        assert_eq!(504, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(508, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(508, queue.intervals.get(1).unwrap().to_id);
@@ -597,8 +944,8 @@ mod tests {
         queue.enqueue(506);
         assert_eq!(queue.intervals.len(), 3);
 
-        assert_eq!(502, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(504, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(502, queue.intervals.first().unwrap().from_id);
+        assert_eq!(504, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(506, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(506, queue.intervals.get(1).unwrap().to_id);
@@ -609,8 +956,8 @@ mod tests {
         queue.enqueue(507);
         assert_eq!(queue.intervals.len(), 2);
 
-        assert_eq!(502, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(504, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(502, queue.intervals.first().unwrap().from_id);
+        assert_eq!(504, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(506, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(508, queue.intervals.get(1).unwrap().to_id);
@@ -628,8 +975,8 @@ mod tests {
         queue.enqueue(507);
         assert_eq!(queue.intervals.len(), 2);
 
-        assert_eq!(502, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(504, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(502, queue.intervals.first().unwrap().from_id);
+        assert_eq!(504, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(506, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(507, queue.intervals.get(1).unwrap().to_id);
@@ -638,8 +985,8 @@ mod tests {
 
         assert_eq!(queue.intervals.len(), 1);
 
-        assert_eq!(502, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(507, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(502, queue.intervals.first().unwrap().from_id);
+        assert_eq!(507, queue.intervals.first().unwrap().to_id);
     }
 
     #[test]
@@ -695,8 +1042,8 @@ mod tests {
         queue.enqueue(6);
 
         assert_eq!(1, queue.intervals.len());
-        assert_eq!(5, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(6, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(5, queue.intervals.first().unwrap().from_id);
+        assert_eq!(6, queue.intervals.first().unwrap().to_id);
         assert_eq!(2, queue.queue_size());
     }
 
@@ -819,8 +1166,8 @@ mod tests {
 
         assert_eq!(1, queue.intervals.len());
 
-        assert_eq!(10, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(15, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(10, queue.intervals.first().unwrap().from_id);
+        assert_eq!(15, queue.intervals.first().unwrap().to_id);
     }
 
     #[test]
@@ -836,8 +1183,8 @@ mod tests {
 
         assert_eq!(2, queue.intervals.len());
 
-        assert_eq!(10, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(15, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(10, queue.intervals.first().unwrap().from_id);
+        assert_eq!(15, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(20, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(25, queue.intervals.get(1).unwrap().to_id);
@@ -856,8 +1203,8 @@ mod tests {
 
         assert_eq!(1, queue.intervals.len());
 
-        assert_eq!(10, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(25, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(10, queue.intervals.first().unwrap().from_id);
+        assert_eq!(25, queue.intervals.first().unwrap().to_id);
     }
 
     #[test]
@@ -873,8 +1220,8 @@ mod tests {
 
         assert_eq!(2, queue.intervals.len());
 
-        assert_eq!(5, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(10, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(5, queue.intervals.first().unwrap().from_id);
+        assert_eq!(10, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(15, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(20, queue.intervals.get(1).unwrap().to_id);
@@ -890,8 +1237,8 @@ mod tests {
 
         assert_eq!(4, queue.intervals.len());
 
-        assert_eq!(10, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(20, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(10, queue.intervals.first().unwrap().from_id);
+        assert_eq!(20, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(30, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(40, queue.intervals.get(1).unwrap().to_id);
@@ -914,8 +1261,8 @@ mod tests {
 
         assert_eq!(5, queue.intervals.len());
 
-        assert_eq!(10, queue.intervals.get(0).unwrap().from_id);
-        assert_eq!(20, queue.intervals.get(0).unwrap().to_id);
+        assert_eq!(10, queue.intervals.first().unwrap().from_id);
+        assert_eq!(20, queue.intervals.first().unwrap().to_id);
 
         assert_eq!(30, queue.intervals.get(1).unwrap().from_id);
         assert_eq!(40, queue.intervals.get(1).unwrap().to_id);
@@ -1289,7 +1636,7 @@ mod tests {
         queue.enqueue(2);
         queue.enqueue(3);
 
-        let iter = queue.iter();
+        let iter = queue.clone().into_iter();
 
         queue.enqueue(4);
         queue.remove(2).unwrap();
@@ -1307,7 +1654,7 @@ mod tests {
         queue.enqueue_range(QueueIndexRange::restore(10, 20));
         queue.enqueue_range(QueueIndexRange::restore(30, 40));
 
-        queue.remove_range(&QueueIndexRange::restore(5, 1000));
+        queue.remove_range(QueueIndexRange::restore(5, 1000));
 
         assert!(queue.is_empty());
         assert_eq!(1, queue.intervals.len());
@@ -1334,7 +1681,13 @@ mod tests {
         let start = i64::MAX - 2;
         queue.enqueue_range(QueueIndexRange::restore(start, i64::MAX));
 
-        queue.remove_range(&QueueIndexRange::restore(i64::MAX - 1, i64::MAX));
+        queue.remove_range(QueueIndexRange::restore(i64::MAX - 1, i64::MAX));
+
+        assert_eq!(1, queue.intervals.len());
+        assert_eq!(start, queue.intervals[0].from_id);
+        assert_eq!(start, queue.intervals[0].to_id);
+
+        queue.remove_range(QueueIndexRange::restore(start, i64::MAX));
 
         assert!(queue.is_empty());
         assert_eq!(1, queue.intervals.len());
@@ -1354,8 +1707,8 @@ mod tests {
     #[test]
     fn len_and_size_on_large_u64_interval() {
         let queue = QueueWithIntervals::from_single_interval(u64::MAX - 2, u64::MAX);
-        assert_eq!(4, queue.len()); // inclusive counting with saturating add near max
-        assert_eq!(4, queue.queue_size());
+        assert_eq!(3, queue.len());
+        assert_eq!(3, queue.queue_size());
         assert_eq!(Some(u64::MAX - 2), queue.get_min_id());
         assert_eq!(Some(u64::MAX), queue.get_max_id());
     }
@@ -1375,4 +1728,428 @@ mod tests {
         // placeholder retains last to_id value
         assert_eq!(3, queue.intervals[0].to_id);
     }
+
+    #[test]
+    fn has_message_with_many_intervals() {
+        let mut queue = QueueWithIntervals::new();
+
+        for i in 0..1000 {
+            queue.enqueue(i * 2);
+        }
+
+        assert_eq!(1000, queue.intervals.len());
+
+        for i in 0..1000 {
+            assert!(queue.has_message(i * 2));
+            assert!(!queue.has_message(i * 2 + 1));
+        }
+
+        assert!(!queue.has_message(-1));
+    }
+
+    #[test]
+    fn contains_and_intersects_range() {
+        let mut queue = QueueWithIntervals::new();
+        queue.enqueue_range(QueueIndexRange::restore(10, 20));
+        queue.enqueue_range(QueueIndexRange::restore(30, 40));
+
+        assert!(queue.contains_range(&QueueIndexRange::restore(10, 20)));
+        assert!(queue.contains_range(&QueueIndexRange::restore(32, 35)));
+        assert!(!queue.contains_range(&QueueIndexRange::restore(15, 30)));
+        assert!(!queue.contains_range(&QueueIndexRange::restore(9, 12)));
+        assert!(!queue.contains_range(&QueueIndexRange::restore(41, 45)));
+        assert!(queue.contains_range(&QueueIndexRange::new_empty(100)));
+
+        assert!(queue.intersects_range(&QueueIndexRange::restore(0, 10)));
+        assert!(queue.intersects_range(&QueueIndexRange::restore(21, 30)));
+        assert!(queue.intersects_range(&QueueIndexRange::restore(0, 100)));
+        assert!(!queue.intersects_range(&QueueIndexRange::restore(21, 29)));
+        assert!(!queue.intersects_range(&QueueIndexRange::restore(41, 100)));
+        assert!(!queue.intersects_range(&QueueIndexRange::new_empty(15)));
+
+        let empty = QueueWithIntervals::<i64>::new();
+        assert!(!empty.contains_range(&QueueIndexRange::restore(-1, 0)));
+        assert!(!empty.intersects_range(&QueueIndexRange::restore(-1, 0)));
+    }
+
+    #[test]
+    fn count_in_range_uses_bounds() {
+        let mut queue = QueueWithIntervals::new();
+        queue.enqueue_range(QueueIndexRange::restore(10, 20));
+        queue.enqueue_range(QueueIndexRange::restore(30, 40));
+
+        assert_eq!(queue.count_in_range(..), 22);
+        assert_eq!(queue.count_in_range(15..35), 11);
+        assert_eq!(queue.count_in_range(15..=35), 12);
+        assert_eq!(queue.count_in_range(21..30), 0);
+        assert_eq!(queue.count_in_range(..=10), 1);
+        assert_eq!(queue.count_in_range(40..), 1);
+
+        assert_eq!(QueueWithIntervals::<i64>::new().count_in_range(..), 0);
+
+        let full = QueueWithIntervals::<u128>::from_single_interval(0, u128::MAX);
+        assert_eq!(full.count_in_range(..), usize::MAX);
+        assert_eq!(full.count_in_range(..10), 10);
+    }
+
+    #[test]
+    fn len_is_tracked_through_mutations() {
+        let mut queue = QueueWithIntervals::new();
+        let mut expected = std::collections::BTreeSet::new();
+
+        let mut seed = 42u64;
+        let mut next = || {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
+            ((seed >> 33) % 100) as i64
+        };
+
+        for step in 0..2000 {
+            let a = next();
+            let b = next();
+            let (from_id, to_id) = (a.min(b), a.max(b));
+
+            match step % 6 {
+                0 => {
+                    queue.enqueue(a);
+                    expected.insert(a);
+                }
+                1 => {
+                    let _ = queue.remove(a);
+                    expected.remove(&a);
+                }
+                2 => {
+                    queue.enqueue_range(QueueIndexRange::restore(from_id, to_id));
+                    expected.extend(from_id..=to_id);
+                }
+                3 => {
+                    queue.remove_range(QueueIndexRange::restore(from_id, to_id));
+                    expected.retain(|v| *v < from_id || *v > to_id);
+                }
+                4 => {
+                    assert_eq!(expected.pop_first(), queue.dequeue());
+                }
+                _ => {
+                    queue.merge(QueueWithIntervals::from_single_interval(a, a + 2));
+                    expected.extend(a..=a + 2);
+                }
+            }
+
+            assert_eq!(expected.len(), queue.len());
+            assert_eq!(expected.len(), queue.queue_size());
+        }
+    }
+
+    #[test]
+    fn len_of_huge_intervals() {
+        let queue = QueueWithIntervals::from_single_interval(0u64, 10_000_000_000);
+        assert_eq!(10_000_000_001, queue.len());
+
+        let mut queue = QueueWithIntervals::from_single_interval(u128::MIN, u128::MAX);
+        assert_eq!(u128::MAX, queue.len_u128());
+        assert_eq!(usize::MAX, queue.len());
+
+        queue.dequeue();
+        assert_eq!(u128::MAX, queue.len_u128());
+
+        queue.remove(u128::MAX).unwrap();
+        assert_eq!(u128::MAX - 1, queue.len_u128());
+
+        queue.enqueue_range(QueueIndexRange::restore(0, u128::MAX));
+        assert_eq!(u128::MAX, queue.len_u128());
+
+        queue.clean();
+        assert_eq!(0, queue.len_u128());
+    }
+
+    #[test]
+    fn len_of_restored_empty_intervals() {
+        let queue = QueueWithIntervals::<i64>::restore(vec![
+            QueueIndexRange::new_empty(0),
+            QueueIndexRange::new_empty(5),
+        ]);
+
+        assert_eq!(0, queue.len_u128());
+        assert_eq!(0, queue.len());
+    }
+
+    #[test]
+    fn try_restore_accepts_canonical_intervals() {
+        let queue = QueueWithIntervals::try_restore(vec![
+            QueueIndexRange::restore(1, 5),
+            QueueIndexRange::restore(7, 7),
+            QueueIndexRange::restore(9, 12),
+        ])
+        .unwrap();
+
+        assert_eq!(3, queue.intervals.len());
+        assert_eq!(10, queue.len());
+
+        assert!(
+            QueueWithIntervals::<i64>::try_restore(vec![])
+                .unwrap()
+                .is_empty()
+        );
+
+        let empty = QueueWithIntervals::<u8>::new();
+        let restored = QueueWithIntervals::try_restore(empty.get_intervals().to_vec()).unwrap();
+        assert!(restored.is_empty());
+    }
+
+    #[test]
+    fn try_restore_rejects_malformed_intervals() {
+        let err = QueueWithIntervals::try_restore(vec![
+            QueueIndexRange::restore(1, 5),
+            QueueIndexRange::new_empty(10),
+        ])
+        .unwrap_err();
+        assert_eq!(
+            InvariantViolation::EmptyInterval {
+                index: 1,
+                from_id: 10,
+                to_id: 9
+            },
+            err
+        );
+
+        let err = QueueWithIntervals::try_restore(vec![
+            QueueIndexRange::restore(10, 20),
+            QueueIndexRange::restore(1, 5),
+        ])
+        .unwrap_err();
+        assert_eq!(
+            InvariantViolation::Unsorted {
+                index: 1,
+                from_id: 1,
+                previous_from_id: 10
+            },
+            err
+        );
+
+        let err = QueueWithIntervals::try_restore(vec![
+            QueueIndexRange::restore(1, 5),
+            QueueIndexRange::restore(1, 5),
+        ])
+        .unwrap_err();
+        assert_eq!(
+            InvariantViolation::Overlapping {
+                index: 1,
+                from_id: 1,
+                previous_to_id: 5
+            },
+            err
+        );
+
+        let err = QueueWithIntervals::try_restore(vec![
+            QueueIndexRange::restore(1, 5),
+            QueueIndexRange::restore(6, 10),
+        ])
+        .unwrap_err();
+        assert_eq!(
+            InvariantViolation::Adjacent {
+                index: 1,
+                from_id: 6,
+                previous_to_id: 5
+            },
+            err
+        );
+        assert_eq!(
+            "Interval #1 starts at 6 right after the previous interval which ends at 5",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn restore_normalized_coalesces_intervals() {
+        let queue = QueueWithIntervals::restore_normalized(vec![
+            QueueIndexRange::restore(30, 40),
+            QueueIndexRange::new_empty(100),
+            QueueIndexRange::restore(1, 5),
+            QueueIndexRange::restore(6, 10),
+            QueueIndexRange::restore(35, 50),
+            QueueIndexRange::restore(3, 4),
+            QueueIndexRange::restore(60, 60),
+        ]);
+
+        assert_eq!(3, queue.intervals.len());
+        assert_eq!(1, queue.intervals[0].from_id);
+        assert_eq!(10, queue.intervals[0].to_id);
+        assert_eq!(30, queue.intervals[1].from_id);
+        assert_eq!(50, queue.intervals[1].to_id);
+        assert_eq!(60, queue.intervals[2].from_id);
+        assert_eq!(60, queue.intervals[2].to_id);
+        assert_eq!(32, queue.len());
+
+        let queue =
+            QueueWithIntervals::<i64>::restore_normalized(vec![QueueIndexRange::new_empty(5)]);
+        assert!(queue.is_empty());
+
+        let queue = QueueWithIntervals::restore_normalized(vec![
+            QueueIndexRange::restore(u8::MAX, u8::MAX),
+            QueueIndexRange::restore(0, u8::MAX - 1),
+        ]);
+        assert_eq!(1, queue.intervals.len());
+        assert_eq!(256, queue.len());
+    }
+
+    #[test]
+    fn try_enqueue_reports_new_values() {
+        let mut queue = QueueWithIntervals::new();
+
+        assert!(queue.try_enqueue(10).unwrap());
+        assert!(queue.try_enqueue(11).unwrap());
+        assert!(!queue.try_enqueue(10).unwrap());
+        assert!(queue.try_enqueue(9).unwrap());
+
+        assert!(
+            queue
+                .try_enqueue_range(QueueIndexRange::restore(20, 30))
+                .unwrap()
+        );
+        assert!(
+            !queue
+                .try_enqueue_range(QueueIndexRange::restore(22, 28))
+                .unwrap()
+        );
+        assert!(
+            queue
+                .try_enqueue_range(QueueIndexRange::restore(5, 25))
+                .unwrap()
+        );
+        assert!(
+            !queue
+                .try_enqueue_range(QueueIndexRange::new_empty(100))
+                .unwrap()
+        );
+
+        assert_eq!(1, queue.intervals.len());
+        assert_eq!(5, queue.intervals[0].from_id);
+        assert_eq!(30, queue.intervals[0].to_id);
+        assert_eq!(26, queue.len());
+    }
+
+    #[test]
+    fn try_enqueue_at_numeric_boundaries() {
+        let mut queue = QueueWithIntervals::<u8>::new();
+
+        assert!(queue.try_enqueue(u8::MAX).unwrap());
+        assert!(queue.try_enqueue(u8::MAX - 1).unwrap());
+        assert!(queue.try_enqueue(u8::MIN).unwrap());
+        assert!(queue.try_enqueue(1).unwrap());
+        assert!(!queue.try_enqueue(u8::MAX).unwrap());
+        assert!(
+            queue
+                .try_enqueue_range(QueueIndexRange::restore(0, u8::MAX))
+                .unwrap()
+        );
+        assert_eq!(256, queue.len());
+
+        let mut queue = QueueWithIntervals::<i64>::new();
+        queue.enqueue(i64::MIN + 1);
+        queue.enqueue(i64::MIN);
+        queue.enqueue(i64::MAX - 1);
+        queue.enqueue(i64::MAX);
+        assert!(!queue.try_enqueue(i64::MIN).unwrap());
+
+        assert_eq!(Some(i64::MIN), queue.get_min_id());
+        assert_eq!(Some(i64::MAX), queue.get_max_id());
+        assert_eq!(4, queue.len());
+    }
+
+    #[test]
+    fn intervals_iter_skips_empty_placeholder() {
+        let mut queue = QueueWithIntervals::new();
+        assert_eq!(0, queue.intervals_iter().count());
+
+        queue.enqueue_range(QueueIndexRange::restore(10, 20));
+        queue.enqueue_range(QueueIndexRange::restore(30, 40));
+
+        let intervals: Vec<(i64, i64)> = queue
+            .intervals_iter()
+            .map(|itm| (itm.from_id, itm.to_id))
+            .collect();
+        assert_eq!(vec![(10, 20), (30, 40)], intervals);
+
+        queue.clean();
+        assert_eq!(0, queue.intervals_iter().count());
+    }
+
+    #[test]
+    fn range_iterates_values_within_bounds() {
+        let mut queue = QueueWithIntervals::new();
+        queue.enqueue_range(QueueIndexRange::restore(10, 20));
+        queue.enqueue_range(QueueIndexRange::restore(30, 40));
+        queue.enqueue_range(QueueIndexRange::restore(50, 60));
+
+        let values: Vec<i64> = queue.range(18..32).collect();
+        assert_eq!(vec![18, 19, 20, 30, 31], values);
+
+        let values: Vec<i64> = queue.range(18..=32).rev().collect();
+        assert_eq!(vec![32, 31, 30, 20, 19, 18], values);
+
+        let values: Vec<i64> = queue.range(58..).collect();
+        assert_eq!(vec![58, 59, 60], values);
+
+        let values: Vec<i64> = queue.range(..=11).collect();
+        assert_eq!(vec![10, 11], values);
+
+        assert_eq!(33, queue.range(..).count());
+        assert_eq!((4, Some(4)), queue.range(19..=31).size_hint());
+
+        assert_eq!(0, queue.range(21..30).count());
+        assert_eq!(0, queue.range(61..).count());
+        assert_eq!(0, queue.range(..10).count());
+        #[allow(clippy::reversed_empty_ranges)]
+        let reversed = queue.range(40..30).count();
+        assert_eq!(0, reversed);
+    }
+
+    #[test]
+    fn range_intervals_clips_to_bounds() {
+        let mut queue = QueueWithIntervals::new();
+        queue.enqueue_range(QueueIndexRange::restore(10, 20));
+        queue.enqueue_range(QueueIndexRange::restore(30, 40));
+        queue.enqueue_range(QueueIndexRange::restore(50, 60));
+
+        let intervals: Vec<(i64, i64)> = queue
+            .range_intervals(15..=55)
+            .map(|itm| (itm.from_id, itm.to_id))
+            .collect();
+        assert_eq!(vec![(15, 20), (30, 40), (50, 55)], intervals);
+
+        let intervals: Vec<(i64, i64)> = queue
+            .range_intervals(35..)
+            .rev()
+            .map(|itm| (itm.from_id, itm.to_id))
+            .collect();
+        assert_eq!(vec![(50, 60), (35, 40)], intervals);
+
+        assert_eq!(0, queue.range_intervals(41..50).count());
+        assert_eq!(
+            0,
+            QueueWithIntervals::<i64>::new().range_intervals(..).count()
+        );
+    }
+
+    #[test]
+    fn range_at_numeric_boundaries() {
+        let mut queue = QueueWithIntervals::<u8>::new();
+        queue.enqueue_range(QueueIndexRange::restore(0, 2));
+        queue.enqueue_range(QueueIndexRange::restore(254, 255));
+
+        let values: Vec<u8> = queue.range(..).collect();
+        assert_eq!(vec![0, 1, 2, 254, 255], values);
+
+        let values: Vec<u8> = queue
+            .range((Bound::Excluded(254), Bound::Unbounded))
+            .collect();
+        assert_eq!(vec![255], values);
+
+        assert_eq!(
+            0,
+            queue
+                .range((Bound::Excluded(255), Bound::Unbounded))
+                .count()
+        );
+        assert_eq!(0, queue.range(..0).count());
+    }
 }
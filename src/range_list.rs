@@ -0,0 +1,232 @@
+use std::{fmt, str::FromStr};
+
+use super::{QueueIndexRange, QueueWithIntervals};
+use crate::QueueValue;
+
+// Text format is a comma separated list of values and ranges, e.g. 1-5,7,9-12 or -10--3,0
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseQueueError {
+    // Nothing between two commas or around a dash. Position is the zero-based index of the item
+    EmptyItem { position: usize },
+    // Item is not a value of the type
+    InvalidValue { item: String },
+    // Range ends before it starts
+    InvertedRange { item: String },
+}
+
+impl fmt::Display for ParseQueueError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseQueueError::EmptyItem { position } => {
+                write!(f, "Item #{} of the range list is empty", position)
+            }
+            ParseQueueError::InvalidValue { item } => {
+                write!(f, "'{}' is not a valid value or range", item)
+            }
+            ParseQueueError::InvertedRange { item } => {
+                write!(f, "Range '{}' ends before it starts", item)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseQueueError {}
+
+impl<T: QueueValue> fmt::Display for QueueIndexRange<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return Ok(());
+        }
+
+        if self.from_id == self.to_id {
+            return write!(f, "{}", self.from_id);
+        }
+
+        write!(f, "{}-{}", self.from_id, self.to_id)
+    }
+}
+
+impl<T: QueueValue + FromStr> FromStr for QueueIndexRange<T> {
+    type Err = ParseQueueError;
+
+    fn from_str(src: &str) -> Result<Self, Self::Err> {
+        if src.trim().is_empty() {
+            return Ok(Self::new_empty(T::zero()));
+        }
+
+        parse_range(src, 0)
+    }
+}
+
+impl<T: QueueValue> fmt::Display for QueueWithIntervals<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, interval) in self.intervals_iter().enumerate() {
+            if index > 0 {
+                write!(f, ",")?;
+            }
+
+            write!(f, "{}", interval)?;
+        }
+
+        Ok(())
+    }
+}
+
+// Items may come in any order and overlap. They are merged the same way enqueue_range does it
+impl<T: QueueValue + FromStr> FromStr for QueueWithIntervals<T> {
+    type Err = ParseQueueError;
+
+    fn from_str(src: &str) -> Result<Self, Self::Err> {
+        let mut result = Self::new();
+
+        if src.trim().is_empty() {
+            return Ok(result);
+        }
+
+        for (position, item) in src.split(',').enumerate() {
+            result.enqueue_range(parse_range(item, position)?);
+        }
+
+        Ok(result)
+    }
+}
+
+fn parse_range<T: QueueValue + FromStr>(
+    item: &str,
+    position: usize,
+) -> Result<QueueIndexRange<T>, ParseQueueError> {
+    let item = item.trim();
+
+    if item.is_empty() {
+        return Err(ParseQueueError::EmptyItem { position });
+    }
+
+    // A dash at the very beginning is the sign of the first value, so the separator is searched after it
+    let separator = item
+        .char_indices()
+        .skip(1)
+        .find(|(_, symbol)| *symbol == '-');
+
+    let (from, to) = match separator {
+        Some((index, _)) => (&item[..index], &item[index + 1..]),
+        None => (item, item),
+    };
+
+    let from_id = parse_value(from, item, position)?;
+    let to_id = parse_value(to, item, position)?;
+
+    if from_id > to_id {
+        return Err(ParseQueueError::InvertedRange {
+            item: item.to_string(),
+        });
+    }
+
+    Ok(QueueIndexRange::restore(from_id, to_id))
+}
+
+fn parse_value<T: FromStr>(value: &str, item: &str, position: usize) -> Result<T, ParseQueueError> {
+    let value = value.trim();
+
+    if value.is_empty() {
+        return Err(ParseQueueError::EmptyItem { position });
+    }
+
+    value.parse().map_err(|_| ParseQueueError::InvalidValue {
+        item: item.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ParseQueueError;
+    use crate::{QueueIndexRange, QueueWithIntervals};
+
+    #[test]
+    fn test_display() {
+        let mut queue = QueueWithIntervals::new();
+        assert_eq!(queue.to_string(), "");
+
+        queue.enqueue_range(QueueIndexRange::restore(1, 5));
+        queue.enqueue(7);
+        queue.enqueue_range(QueueIndexRange::restore(9, 12));
+        assert_eq!(queue.to_string(), "1-5,7,9-12");
+
+        queue.enqueue_range(QueueIndexRange::restore(-10, -3));
+        queue.enqueue(-1);
+        assert_eq!(queue.to_string(), "-10--3,-1,1-5,7,9-12");
+
+        assert_eq!(QueueIndexRange::restore(-3, 4).to_string(), "-3-4");
+        assert_eq!(QueueIndexRange::new_empty(5).to_string(), "");
+    }
+
+    #[test]
+    fn test_parse() {
+        let queue: QueueWithIntervals<i64> = "1-5,7,9-12".parse().unwrap();
+        assert_eq!(queue.to_string(), "1-5,7,9-12");
+        assert_eq!(queue.len(), 10);
+
+        let queue: QueueWithIntervals<i64> = " -10--3 , -1, 0-2 ,3".parse().unwrap();
+        assert_eq!(queue.to_string(), "-10--3,-1-3");
+
+        let queue: QueueWithIntervals<i64> = "9-12,1-5,4-8".parse().unwrap();
+        assert_eq!(queue.to_string(), "1-12");
+
+        let queue: QueueWithIntervals<u8> = "".parse().unwrap();
+        assert!(queue.is_empty());
+
+        let queue: QueueWithIntervals<i8> = "-128--127,127".parse().unwrap();
+        assert_eq!(queue.to_string(), "-128--127,127");
+
+        let range: QueueIndexRange<i32> = "-7-7".parse().unwrap();
+        assert_eq!((range.from_id, range.to_id), (-7, 7));
+
+        let range: QueueIndexRange<i32> = "".parse().unwrap();
+        assert!(range.is_empty());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        fn parse(src: &str) -> ParseQueueError {
+            src.parse::<QueueWithIntervals<u8>>().unwrap_err()
+        }
+
+        assert_eq!(parse("1,,2"), ParseQueueError::EmptyItem { position: 1 });
+        assert_eq!(parse("1-"), ParseQueueError::EmptyItem { position: 0 });
+        assert_eq!(
+            parse("é-1"),
+            ParseQueueError::InvalidValue {
+                item: "é-1".to_string()
+            }
+        );
+        assert_eq!(
+            parse("1,x"),
+            ParseQueueError::InvalidValue {
+                item: "x".to_string()
+            }
+        );
+        assert_eq!(
+            parse("1-256"),
+            ParseQueueError::InvalidValue {
+                item: "1-256".to_string()
+            }
+        );
+        assert_eq!(
+            parse("-1"),
+            ParseQueueError::InvalidValue {
+                item: "-1".to_string()
+            }
+        );
+        assert_eq!(
+            parse("5-1"),
+            ParseQueueError::InvertedRange {
+                item: "5-1".to_string()
+            }
+        );
+
+        assert_eq!(
+            parse("5-1").to_string(),
+            "Range '5-1' ends before it starts"
+        );
+    }
+}
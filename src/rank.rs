@@ -0,0 +1,154 @@
+use super::QueueWithIntervals;
+use crate::QueueValue;
+
+impl<T: QueueValue> QueueWithIntervals<T> {
+    /// Returns the value at the zero-based position n in ascending order.
+    /// O(log n) over the intervals, but the first call after a mutation rebuilds the prefix counts
+    /// in O(n), since every mutation drops them
+    pub fn nth(&self, n: usize) -> Option<T> {
+        let intervals = self.get_non_empty_intervals();
+        let prefix_counts = self.get_prefix_counts();
+        let n = n as u128;
+
+        if n >= *prefix_counts.last().unwrap() {
+            return None;
+        }
+
+        let index = prefix_counts.partition_point(|count| *count <= n) - 1;
+        let interval = intervals.get(index)?;
+
+        Some(interval.from_id.add_distance(n - prefix_counts[index]))
+    }
+
+    /// Returns the amount of values strictly below the value, saturating at usize::MAX.
+    /// Same cost as nth: O(log n) over the intervals plus an O(n) rebuild after a mutation
+    pub fn rank(&self, value: T) -> usize {
+        let intervals = self.get_non_empty_intervals();
+        let index = intervals.partition_point(|itm| itm.to_id < value);
+
+        let mut result = self.get_prefix_counts()[index];
+
+        if let Some(interval) = intervals.get(index)
+            && interval.from_id < value
+        {
+            result = result.saturating_add(T::distance(interval.from_id, value));
+        }
+
+        usize::try_from(result).unwrap_or(usize::MAX)
+    }
+
+    /// Returns the zero-based position of the value if it is in the queue
+    pub fn position(&self, value: T) -> Option<usize> {
+        if !self.has_message(value) {
+            return None;
+        }
+
+        Some(self.rank(value))
+    }
+
+    // Amount of values before each non-empty interval, followed by the total. Saturates at u128::MAX
+    fn get_prefix_counts(&self) -> &[u128] {
+        self.prefix_counts.get_or_init(|| {
+            let intervals = self.get_non_empty_intervals();
+            let mut result = Vec::with_capacity(intervals.len() + 1);
+            let mut total = 0u128;
+            result.push(total);
+
+            for interval in intervals {
+                total = total.saturating_add(interval.len_u128());
+                result.push(total);
+            }
+
+            result
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{QueueIndexRange, QueueWithIntervals};
+
+    fn queue(intervals: &[(i64, i64)]) -> QueueWithIntervals<i64> {
+        let mut result = QueueWithIntervals::new();
+
+        for (from_id, to_id) in intervals {
+            result.enqueue_range(QueueIndexRange::restore(*from_id, *to_id));
+        }
+
+        result
+    }
+
+    #[test]
+    fn test_nth() {
+        let queue = queue(&[(5, 10), (20, 20), (30, 39)]);
+
+        assert_eq!(queue.nth(0), Some(5));
+        assert_eq!(queue.nth(5), Some(10));
+        assert_eq!(queue.nth(6), Some(20));
+        assert_eq!(queue.nth(7), Some(30));
+        assert_eq!(queue.nth(16), Some(39));
+        assert_eq!(queue.nth(17), None);
+
+        assert_eq!(QueueWithIntervals::<i64>::new().nth(0), None);
+    }
+
+    #[test]
+    fn test_rank_and_position() {
+        let queue = queue(&[(5, 10), (20, 20), (30, 39)]);
+
+        assert_eq!(queue.rank(i64::MIN), 0);
+        assert_eq!(queue.rank(5), 0);
+        assert_eq!(queue.rank(8), 3);
+        assert_eq!(queue.rank(15), 6);
+        assert_eq!(queue.rank(20), 6);
+        assert_eq!(queue.rank(21), 7);
+        assert_eq!(queue.rank(i64::MAX), 17);
+
+        assert_eq!(queue.position(8), Some(3));
+        assert_eq!(queue.position(30), Some(7));
+        assert_eq!(queue.position(15), None);
+
+        assert_eq!(QueueWithIntervals::<i64>::new().rank(10), 0);
+    }
+
+    #[test]
+    fn test_prefix_counts_follow_mutations() {
+        let mut queue = queue(&[(5, 10), (20, 20)]);
+
+        assert_eq!(queue.nth(6), Some(20));
+
+        queue.remove(5).unwrap();
+        assert_eq!(queue.nth(5), Some(20));
+
+        queue.enqueue(15);
+        assert_eq!(queue.rank(20), 6);
+
+        queue.remove_range(QueueIndexRange::restore(0, 15));
+        assert_eq!(queue.nth(0), Some(20));
+
+        queue.enqueue_range(QueueIndexRange::restore(0, 1));
+        assert_eq!(queue.position(20), Some(2));
+
+        queue.dequeue();
+        assert_eq!(queue.position(20), Some(1));
+
+        queue.clean();
+        assert_eq!(queue.nth(0), None);
+    }
+
+    #[test]
+    fn test_nth_at_numeric_boundaries() {
+        let mut queue = QueueWithIntervals::<u8>::new();
+        queue.enqueue_range(QueueIndexRange::restore(0, 2));
+        queue.enqueue_range(QueueIndexRange::restore(250, 255));
+
+        assert_eq!(queue.nth(3), Some(250));
+        assert_eq!(queue.nth(8), Some(255));
+        assert_eq!(queue.rank(255), 8);
+
+        let queue = QueueWithIntervals::<i128>::from_single_interval(i128::MIN, i128::MAX);
+        assert_eq!(queue.nth(usize::MAX), Some(i128::MIN + usize::MAX as i128));
+        assert_eq!(queue.rank(i128::MAX), usize::MAX);
+        assert_eq!(queue.position(i128::MIN), Some(0));
+    }
+}
@@ -1,4 +1,6 @@
-use super::{IndexRange, QueueIndexRange, QueueWithIntervals};
+use super::{
+    IndexRange, QueueIndexRange, QueueWithIntervals, queue_with_intervals::saturate_count,
+};
 use crate::QueueValue;
 
 pub enum IndexToRemoveRange<T: QueueValue> {
@@ -16,70 +18,111 @@ pub enum IndexToRemoveRange<T: QueueValue> {
 }
 
 impl<T: QueueValue> QueueWithIntervals<T> {
-    pub fn remove_range(&mut self, range_to_remove: &QueueIndexRange<T>) {
+    pub fn remove_range(&mut self, range_to_remove: impl Into<QueueIndexRange<T>>) {
+        self.remove_range_and_count(&range_to_remove.into());
+    }
+
+    /// Removes the range and returns the amount of removed values, saturating at usize::MAX
+    pub fn remove_range_count(&mut self, range_to_remove: impl Into<QueueIndexRange<T>>) -> usize {
+        let was_empty = self.is_empty();
+        let removed = self.remove_range_and_count(&range_to_remove.into());
+
+        // A zero amount removed something only if it emptied the queue
+        saturate_count(removed, !was_empty && self.is_empty())
+    }
+
+    /// Removes the range and returns the sub-ranges of it which were in the queue
+    pub fn remove_range_pieces(
+        &mut self,
+        range_to_remove: impl Into<QueueIndexRange<T>>,
+    ) -> Vec<QueueIndexRange<T>> {
+        let range_to_remove = range_to_remove.into();
+
         if range_to_remove.is_empty() {
-            return;
+            return vec![];
+        }
+
+        let removed = self
+            .range_intervals(range_to_remove.from_id..=range_to_remove.to_id)
+            .collect();
+
+        self.remove_range_and_count(&range_to_remove);
+
+        removed
+    }
+
+    // Returns the amount of removed values modulo 2^128
+    fn remove_range_and_count(&mut self, range_to_remove: &QueueIndexRange<T>) -> u128 {
+        if range_to_remove.is_empty() {
+            return 0;
+        }
+
+        let values_to_remove = self.count_values_in_range(range_to_remove);
+
+        if values_to_remove == 0 && !self.intersects_range(range_to_remove) {
+            return 0;
         }
+
+        self.values_count = self.values_count.wrapping_sub(values_to_remove);
+
         let (from_index, to_index) = IndexRange::new(&self.intervals, range_to_remove);
 
         match from_index {
             IndexRange::Exact(exact_index) => {
                 self.left_is_exact(exact_index, to_index, range_to_remove);
             }
-            IndexRange::First => return self.left_is_between(0, to_index, range_to_remove),
+            IndexRange::First => self.left_is_between(0, to_index, range_to_remove),
             IndexRange::Last => match to_index {
                 IndexRange::Exact(_) => {
                     // Range starts after all intervals and ends in an interval - this shouldn't happen
                     // as Last means from_id is after all intervals, so to_id can't be in an interval
                     // This case is logically impossible, but we handle it gracefully by doing nothing
-                    return;
                 }
                 IndexRange::First => {
                     // Range starts after all intervals and ends before first interval - impossible
                     // This case is logically impossible, but we handle it gracefully by doing nothing
-                    return;
                 }
-                IndexRange::Last => return,
+                IndexRange::Last => (),
                 IndexRange::Between {
                     left_index: _left_index,
                     right_index: _right_index,
                 } => {
                     // Range starts after all intervals and ends between intervals - impossible
                     // This case is logically impossible, but we handle it gracefully by doing nothing
-                    return;
                 }
                 IndexRange::JoinToIndexFrom(_) => {
                     // Range starts after all intervals and ends just before an interval - impossible
                     // This case is logically impossible, but we handle it gracefully by doing nothing
-                    return;
                 }
                 IndexRange::JoinToIndexTo(_) => {
                     // Range starts after all intervals and ends just after an interval - impossible
                     // This case is logically impossible, but we handle it gracefully by doing nothing
-                    return;
                 }
                 IndexRange::MergeIntervals(_index) => {
                     // Range starts after all intervals and ends at merge point - impossible
                     // This case is logically impossible, but we handle it gracefully by doing nothing
-                    return;
                 }
             },
             IndexRange::Between {
                 left_index: _,
                 right_index,
-            } => return self.left_is_between(right_index, to_index, range_to_remove),
+            } => self.left_is_between(right_index, to_index, range_to_remove),
             IndexRange::JoinToIndexFrom(from_index) => {
-                return self.left_is_between(from_index, to_index, range_to_remove);
+                self.left_is_between(from_index, to_index, range_to_remove)
             }
             IndexRange::JoinToIndexTo(from_index) => {
-                return self.left_is_between(from_index + 1, to_index, range_to_remove);
+                self.left_is_between(from_index + 1, to_index, range_to_remove)
             }
             IndexRange::MergeIntervals(from_index) => {
                 // Range starts at a merge point (between two intervals that could be merged)
                 // We need to remove intervals starting from the merge point
-                return self.left_is_between(from_index + 1, to_index, range_to_remove);
+                self.left_is_between(from_index + 1, to_index, range_to_remove)
             }
         }
+
+        self.after_mutation("remove_range");
+
+        values_to_remove
     }
 
     fn left_is_between(
@@ -90,7 +133,7 @@ impl<T: QueueValue> QueueWithIntervals<T> {
     ) {
         match to_index {
             IndexRange::Exact(index) => {
-                self.remove_and_update(from_index, index, range_to_remove.into());
+                self.remove_and_update(from_index, index, range_to_remove);
             }
             IndexRange::JoinToIndexFrom(index) => {
                 if index > from_index {
@@ -126,83 +169,51 @@ impl<T: QueueValue> QueueWithIntervals<T> {
         to_index: IndexRange,
         range_to_remove: &QueueIndexRange<T>,
     ) {
-        match to_index {
+        // Exclusive index of the last touched interval plus the part of it which survives the removal.
+        let (to_index, right_part) = match to_index {
             IndexRange::Exact(to_index) => {
-                if to_index == from_index {
-                    let item = self.intervals.get_mut(from_index).unwrap();
-
-                    // Remove the tail starting from range_to_remove.from_id through range_to_remove.to_id.
-                    if range_to_remove.to_id >= item.to_id {
-                        item.make_empty();
-                    } else if range_to_remove.from_id <= item.from_id {
-                        if let Some(to_plus_one) = range_to_remove.to_id.checked_add_one() {
-                            item.from_id = to_plus_one;
-                        } else {
-                            panic!("Cannot increment to_id beyond max value");
-                        }
-                    } else {
-                        if let Some(from_minus_one) = range_to_remove.from_id.checked_sub_one() {
-                            item.to_id = from_minus_one;
-                        } else {
-                            panic!("Cannot decrement from_id below zero");
-                        }
-                    }
-
-                    // Remove all intervals after the current one, since range extends beyond or ends here.
-                    if self.intervals.len() > from_index + 1 {
-                        self.remove_intervals(from_index + 1, self.intervals.len());
-                    }
+                let item = self.intervals.get(to_index).unwrap();
+
+                let right_part = if range_to_remove.to_id < item.to_id {
+                    Some(QueueIndexRange {
+                        from_id: range_to_remove.to_id + T::one(),
+                        to_id: item.to_id,
+                    })
                 } else {
-                    let item = self.intervals.get_mut(to_index).unwrap();
-
-                    if range_to_remove.to_id >= item.to_id {
-                        // Range ends at or after the end of this interval; drop it completely.
-                        self.remove_intervals(from_index + 1, to_index + 1);
-                    } else {
-                        // Trim the left part of the target interval and remove the ones in between.
-                        if let Some(to_plus_one) = range_to_remove.to_id.checked_add_one() {
-                            item.from_id = to_plus_one;
-                        } else {
-                            panic!("Cannot increment to_id beyond max value");
-                        }
-                        self.remove_intervals(from_index + 1, to_index);
-                    }
-                }
+                    None
+                };
+
+                (to_index + 1, right_part)
             }
             IndexRange::First => {
                 return self.left_is_between(from_index, to_index, range_to_remove);
             }
-            IndexRange::Last => {
-                // Remove everything after the starting interval.
-                self.remove_intervals(from_index + 1, self.intervals.len());
-            }
+            IndexRange::Last => (self.intervals.len(), None),
             IndexRange::Between {
-                left_index,
+                left_index: _,
                 right_index,
-            } => {
-                // Start removal right after the starting interval.
-                return self.left_is_between(
-                    from_index + 1,
-                    IndexRange::Between {
-                        left_index,
-                        right_index,
-                    },
-                    range_to_remove,
-                );
-            }
-            IndexRange::JoinToIndexFrom(index) => {
-                // Remove intervals fully covered before the join target (not including it).
-                self.remove_intervals(from_index + 1, index);
-            }
-            IndexRange::JoinToIndexTo(index) => {
-                // Remove intervals up to and including the interval that ends exactly at to_id.
-                self.remove_intervals(from_index + 1, index + 1);
-            }
-            IndexRange::MergeIntervals(_index) => {
-                // Remove intervals that are fully covered when the end touches a merge point.
-                self.remove_intervals(from_index + 1, _index + 1);
-            }
-        }
+            } => (right_index, None),
+            IndexRange::JoinToIndexFrom(index) => (index, None),
+            IndexRange::JoinToIndexTo(index) => (index + 1, None),
+            IndexRange::MergeIntervals(index) => (index + 1, None),
+        };
+
+        let item = self.intervals.get(from_index).unwrap();
+
+        let left_part = if item.from_id < range_to_remove.from_id {
+            Some(QueueIndexRange {
+                from_id: item.from_id,
+                to_id: range_to_remove.from_id - T::one(),
+            })
+        } else {
+            None
+        };
+
+        self.replace_intervals(
+            from_index,
+            to_index,
+            left_part.into_iter().chain(right_part),
+        );
     }
 
     fn remove_and_update(
@@ -227,10 +238,23 @@ impl<T: QueueValue> QueueWithIntervals<T> {
     }
 
     fn remove_intervals(&mut self, from_index: usize, to_index: usize) {
-        for _ in from_index..to_index {
-            if self.intervals.len() > 1 {
-                self.intervals.remove(from_index);
-            }
+        self.replace_intervals(from_index, to_index, std::iter::empty());
+    }
+
+    fn replace_intervals(
+        &mut self,
+        from_index: usize,
+        to_index: usize,
+        parts_to_keep: impl Iterator<Item = QueueIndexRange<T>>,
+    ) {
+        if from_index >= to_index {
+            return;
+        }
+
+        self.intervals.splice(from_index..to_index, parts_to_keep);
+
+        if self.intervals.is_empty() {
+            self.intervals.push(QueueIndexRange::new_empty(T::zero()));
         }
     }
 }
@@ -298,8 +322,8 @@ mod tests_basics {
         assert_eq!(queue.intervals.len(), 4);
 
         // Verify intervals are unchanged
-        assert_eq!(queue.intervals.get(0).unwrap().from_id, 10);
-        assert_eq!(queue.intervals.get(0).unwrap().to_id, 20);
+        assert_eq!(queue.intervals.first().unwrap().from_id, 10);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 20);
         assert_eq!(queue.intervals.get(1).unwrap().from_id, 30);
         assert_eq!(queue.intervals.get(1).unwrap().to_id, 40);
         assert_eq!(queue.intervals.get(2).unwrap().from_id, 50);
@@ -326,8 +350,8 @@ mod tests_left_is_first {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 3);
 
-        assert_eq!(queue.intervals.get(0).unwrap().from_id, 30);
-        assert_eq!(queue.intervals.get(0).unwrap().to_id, 40);
+        assert_eq!(queue.intervals.first().unwrap().from_id, 30);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 40);
 
         assert_eq!(queue.intervals.get(1).unwrap().from_id, 50);
         assert_eq!(queue.intervals.get(1).unwrap().to_id, 60);
@@ -349,8 +373,8 @@ mod tests_left_is_first {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 2);
 
-        assert_eq!(queue.intervals.get(0).unwrap().from_id, 50);
-        assert_eq!(queue.intervals.get(0).unwrap().to_id, 60);
+        assert_eq!(queue.intervals.first().unwrap().from_id, 50);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 60);
 
         assert_eq!(queue.intervals.get(1).unwrap().from_id, 70);
         assert_eq!(queue.intervals.get(1).unwrap().to_id, 80);
@@ -369,8 +393,8 @@ mod tests_left_is_first {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 4);
 
-        assert_eq!(queue.intervals.get(0).unwrap().from_id, 11);
-        assert_eq!(queue.intervals.get(0).unwrap().to_id, 20);
+        assert_eq!(queue.intervals.first().unwrap().from_id, 11);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 20);
 
         assert_eq!(queue.intervals.get(1).unwrap().from_id, 30);
         assert_eq!(queue.intervals.get(1).unwrap().to_id, 40);
@@ -395,8 +419,8 @@ mod tests_left_is_first {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 3);
 
-        assert_eq!(queue.intervals.get(0).unwrap().from_id, 30);
-        assert_eq!(queue.intervals.get(0).unwrap().to_id, 40);
+        assert_eq!(queue.intervals.first().unwrap().from_id, 30);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 40);
 
         assert_eq!(queue.intervals.get(1).unwrap().from_id, 50);
         assert_eq!(queue.intervals.get(1).unwrap().to_id, 60);
@@ -418,8 +442,8 @@ mod tests_left_is_first {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 3);
 
-        assert_eq!(queue.intervals.get(0).unwrap().from_id, 34);
-        assert_eq!(queue.intervals.get(0).unwrap().to_id, 40);
+        assert_eq!(queue.intervals.first().unwrap().from_id, 34);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 40);
 
         assert_eq!(queue.intervals.get(1).unwrap().from_id, 50);
         assert_eq!(queue.intervals.get(1).unwrap().to_id, 60);
@@ -441,8 +465,8 @@ mod tests_left_is_first {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 2);
 
-        assert_eq!(queue.intervals.get(0).unwrap().from_id, 50);
-        assert_eq!(queue.intervals.get(0).unwrap().to_id, 60);
+        assert_eq!(queue.intervals.first().unwrap().from_id, 50);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 60);
 
         assert_eq!(queue.intervals.get(1).unwrap().from_id, 70);
         assert_eq!(queue.intervals.get(1).unwrap().to_id, 80);
@@ -461,7 +485,7 @@ mod tests_left_is_first {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 1);
 
-        assert_eq!(queue.intervals.get(0).unwrap().is_empty(), true);
+        assert!(queue.intervals.first().unwrap().is_empty());
     }
 
     #[test]
@@ -477,7 +501,7 @@ mod tests_left_is_first {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 1);
 
-        assert_eq!(queue.intervals.get(0).unwrap().is_empty(), true);
+        assert!(queue.intervals.first().unwrap().is_empty());
     }
 
     #[test]
@@ -493,8 +517,8 @@ mod tests_left_is_first {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 3);
 
-        assert_eq!(queue.intervals.get(0).unwrap().from_id, 22);
-        assert_eq!(queue.intervals.get(0).unwrap().to_id, 40);
+        assert_eq!(queue.intervals.first().unwrap().from_id, 22);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 40);
 
         assert_eq!(queue.intervals.get(1).unwrap().from_id, 50);
         assert_eq!(queue.intervals.get(1).unwrap().to_id, 60);
@@ -516,8 +540,8 @@ mod tests_left_is_first {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 2);
 
-        assert_eq!(queue.intervals.get(0).unwrap().from_id, 42);
-        assert_eq!(queue.intervals.get(0).unwrap().to_id, 60);
+        assert_eq!(queue.intervals.first().unwrap().from_id, 42);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 60);
 
         assert_eq!(queue.intervals.get(1).unwrap().from_id, 70);
         assert_eq!(queue.intervals.get(1).unwrap().to_id, 80);
@@ -536,8 +560,8 @@ mod tests_left_is_first {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 3);
 
-        assert_eq!(queue.intervals.get(0).unwrap().from_id, 30);
-        assert_eq!(queue.intervals.get(0).unwrap().to_id, 40);
+        assert_eq!(queue.intervals.first().unwrap().from_id, 30);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 40);
 
         assert_eq!(queue.intervals.get(1).unwrap().from_id, 50);
         assert_eq!(queue.intervals.get(1).unwrap().to_id, 60);
@@ -559,8 +583,8 @@ mod tests_left_is_first {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 2);
 
-        assert_eq!(queue.intervals.get(0).unwrap().from_id, 50);
-        assert_eq!(queue.intervals.get(0).unwrap().to_id, 60);
+        assert_eq!(queue.intervals.first().unwrap().from_id, 50);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 60);
 
         assert_eq!(queue.intervals.get(1).unwrap().from_id, 70);
         assert_eq!(queue.intervals.get(1).unwrap().to_id, 80);
@@ -584,8 +608,8 @@ mod tests_left_is_between {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 3);
 
-        assert_eq!(queue.intervals.get(0).unwrap().from_id, 10);
-        assert_eq!(queue.intervals.get(0).unwrap().to_id, 20);
+        assert_eq!(queue.intervals.first().unwrap().from_id, 10);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 20);
 
         assert_eq!(queue.intervals.get(1).unwrap().from_id, 50);
         assert_eq!(queue.intervals.get(1).unwrap().to_id, 60);
@@ -607,8 +631,8 @@ mod tests_left_is_between {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 2);
 
-        assert_eq!(queue.intervals.get(0).unwrap().from_id, 10);
-        assert_eq!(queue.intervals.get(0).unwrap().to_id, 20);
+        assert_eq!(queue.intervals.first().unwrap().from_id, 10);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 20);
 
         assert_eq!(queue.intervals.get(1).unwrap().from_id, 70);
         assert_eq!(queue.intervals.get(1).unwrap().to_id, 80);
@@ -627,8 +651,8 @@ mod tests_left_is_between {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 4);
 
-        assert_eq!(queue.intervals.get(0).unwrap().from_id, 10);
-        assert_eq!(queue.intervals.get(0).unwrap().to_id, 20);
+        assert_eq!(queue.intervals.first().unwrap().from_id, 10);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 20);
 
         assert_eq!(queue.intervals.get(1).unwrap().from_id, 31);
         assert_eq!(queue.intervals.get(1).unwrap().to_id, 40);
@@ -653,8 +677,8 @@ mod tests_left_is_between {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 3);
 
-        assert_eq!(queue.intervals.get(0).unwrap().from_id, 10);
-        assert_eq!(queue.intervals.get(0).unwrap().to_id, 20);
+        assert_eq!(queue.intervals.first().unwrap().from_id, 10);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 20);
 
         assert_eq!(queue.intervals.get(1).unwrap().from_id, 50);
         assert_eq!(queue.intervals.get(1).unwrap().to_id, 60);
@@ -676,8 +700,8 @@ mod tests_left_is_between {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 3);
 
-        assert_eq!(queue.intervals.get(0).unwrap().from_id, 10);
-        assert_eq!(queue.intervals.get(0).unwrap().to_id, 20);
+        assert_eq!(queue.intervals.first().unwrap().from_id, 10);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 20);
 
         assert_eq!(queue.intervals.get(1).unwrap().from_id, 54);
         assert_eq!(queue.intervals.get(1).unwrap().to_id, 60);
@@ -699,8 +723,8 @@ mod tests_left_is_between {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 2);
 
-        assert_eq!(queue.intervals.get(0).unwrap().from_id, 10);
-        assert_eq!(queue.intervals.get(0).unwrap().to_id, 20);
+        assert_eq!(queue.intervals.first().unwrap().from_id, 10);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 20);
 
         assert_eq!(queue.intervals.get(1).unwrap().from_id, 70);
         assert_eq!(queue.intervals.get(1).unwrap().to_id, 80);
@@ -719,8 +743,8 @@ mod tests_left_is_between {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 1);
 
-        assert_eq!(queue.intervals.get(0).unwrap().from_id, 10);
-        assert_eq!(queue.intervals.get(0).unwrap().to_id, 20);
+        assert_eq!(queue.intervals.first().unwrap().from_id, 10);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 20);
     }
 
     #[test]
@@ -736,8 +760,8 @@ mod tests_left_is_between {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 1);
 
-        assert_eq!(queue.intervals.get(0).unwrap().from_id, 10);
-        assert_eq!(queue.intervals.get(0).unwrap().to_id, 20);
+        assert_eq!(queue.intervals.first().unwrap().from_id, 10);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 20);
     }
 
     #[test]
@@ -753,8 +777,8 @@ mod tests_left_is_between {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 3);
 
-        assert_eq!(queue.intervals.get(0).unwrap().from_id, 10);
-        assert_eq!(queue.intervals.get(0).unwrap().to_id, 20);
+        assert_eq!(queue.intervals.first().unwrap().from_id, 10);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 20);
 
         assert_eq!(queue.intervals.get(1).unwrap().from_id, 42);
         assert_eq!(queue.intervals.get(1).unwrap().to_id, 60);
@@ -776,8 +800,8 @@ mod tests_left_is_between {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 2);
 
-        assert_eq!(queue.intervals.get(0).unwrap().from_id, 10);
-        assert_eq!(queue.intervals.get(0).unwrap().to_id, 20);
+        assert_eq!(queue.intervals.first().unwrap().from_id, 10);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 20);
 
         assert_eq!(queue.intervals.get(1).unwrap().from_id, 62);
         assert_eq!(queue.intervals.get(1).unwrap().to_id, 80);
@@ -796,8 +820,8 @@ mod tests_left_is_between {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 3);
 
-        assert_eq!(queue.intervals.get(0).unwrap().from_id, 10);
-        assert_eq!(queue.intervals.get(0).unwrap().to_id, 20);
+        assert_eq!(queue.intervals.first().unwrap().from_id, 10);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 20);
 
         assert_eq!(queue.intervals.get(1).unwrap().from_id, 50);
         assert_eq!(queue.intervals.get(1).unwrap().to_id, 60);
@@ -819,8 +843,8 @@ mod tests_left_is_between {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 2);
 
-        assert_eq!(queue.intervals.get(0).unwrap().from_id, 10);
-        assert_eq!(queue.intervals.get(0).unwrap().to_id, 20);
+        assert_eq!(queue.intervals.first().unwrap().from_id, 10);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 20);
 
         assert_eq!(queue.intervals.get(1).unwrap().from_id, 70);
         assert_eq!(queue.intervals.get(1).unwrap().to_id, 80);
@@ -846,8 +870,8 @@ mod tests_left_is_joining_to_from_id {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 3);
 
-        assert_eq!(queue.intervals.get(0).unwrap().from_id, 30);
-        assert_eq!(queue.intervals.get(0).unwrap().to_id, 40);
+        assert_eq!(queue.intervals.first().unwrap().from_id, 30);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 40);
 
         assert_eq!(queue.intervals.get(1).unwrap().from_id, 50);
         assert_eq!(queue.intervals.get(1).unwrap().to_id, 60);
@@ -869,8 +893,8 @@ mod tests_left_is_joining_to_from_id {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 2);
 
-        assert_eq!(queue.intervals.get(0).unwrap().from_id, 50);
-        assert_eq!(queue.intervals.get(0).unwrap().to_id, 60);
+        assert_eq!(queue.intervals.first().unwrap().from_id, 50);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 60);
 
         assert_eq!(queue.intervals.get(1).unwrap().from_id, 70);
         assert_eq!(queue.intervals.get(1).unwrap().to_id, 80);
@@ -889,8 +913,8 @@ mod tests_left_is_joining_to_from_id {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 4);
 
-        assert_eq!(queue.intervals.get(0).unwrap().from_id, 11);
-        assert_eq!(queue.intervals.get(0).unwrap().to_id, 20);
+        assert_eq!(queue.intervals.first().unwrap().from_id, 11);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 20);
 
         assert_eq!(queue.intervals.get(1).unwrap().from_id, 30);
         assert_eq!(queue.intervals.get(1).unwrap().to_id, 40);
@@ -915,8 +939,8 @@ mod tests_left_is_joining_to_from_id {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 3);
 
-        assert_eq!(queue.intervals.get(0).unwrap().from_id, 30);
-        assert_eq!(queue.intervals.get(0).unwrap().to_id, 40);
+        assert_eq!(queue.intervals.first().unwrap().from_id, 30);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 40);
 
         assert_eq!(queue.intervals.get(1).unwrap().from_id, 50);
         assert_eq!(queue.intervals.get(1).unwrap().to_id, 60);
@@ -938,8 +962,8 @@ mod tests_left_is_joining_to_from_id {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 3);
 
-        assert_eq!(queue.intervals.get(0).unwrap().from_id, 34);
-        assert_eq!(queue.intervals.get(0).unwrap().to_id, 40);
+        assert_eq!(queue.intervals.first().unwrap().from_id, 34);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 40);
 
         assert_eq!(queue.intervals.get(1).unwrap().from_id, 50);
         assert_eq!(queue.intervals.get(1).unwrap().to_id, 60);
@@ -961,8 +985,8 @@ mod tests_left_is_joining_to_from_id {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 2);
 
-        assert_eq!(queue.intervals.get(0).unwrap().from_id, 50);
-        assert_eq!(queue.intervals.get(0).unwrap().to_id, 60);
+        assert_eq!(queue.intervals.first().unwrap().from_id, 50);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 60);
 
         assert_eq!(queue.intervals.get(1).unwrap().from_id, 70);
         assert_eq!(queue.intervals.get(1).unwrap().to_id, 80);
@@ -981,7 +1005,7 @@ mod tests_left_is_joining_to_from_id {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 1);
 
-        assert_eq!(queue.intervals.get(0).unwrap().is_empty(), true);
+        assert!(queue.intervals.first().unwrap().is_empty());
     }
 
     #[test]
@@ -997,7 +1021,7 @@ mod tests_left_is_joining_to_from_id {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 1);
 
-        assert_eq!(queue.intervals.get(0).unwrap().is_empty(), true);
+        assert!(queue.intervals.first().unwrap().is_empty());
     }
 
     #[test]
@@ -1013,8 +1037,8 @@ mod tests_left_is_joining_to_from_id {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 3);
 
-        assert_eq!(queue.intervals.get(0).unwrap().from_id, 22);
-        assert_eq!(queue.intervals.get(0).unwrap().to_id, 40);
+        assert_eq!(queue.intervals.first().unwrap().from_id, 22);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 40);
 
         assert_eq!(queue.intervals.get(1).unwrap().from_id, 50);
         assert_eq!(queue.intervals.get(1).unwrap().to_id, 60);
@@ -1036,8 +1060,8 @@ mod tests_left_is_joining_to_from_id {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 2);
 
-        assert_eq!(queue.intervals.get(0).unwrap().from_id, 42);
-        assert_eq!(queue.intervals.get(0).unwrap().to_id, 60);
+        assert_eq!(queue.intervals.first().unwrap().from_id, 42);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 60);
 
         assert_eq!(queue.intervals.get(1).unwrap().from_id, 70);
         assert_eq!(queue.intervals.get(1).unwrap().to_id, 80);
@@ -1056,8 +1080,8 @@ mod tests_left_is_joining_to_from_id {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 3);
 
-        assert_eq!(queue.intervals.get(0).unwrap().from_id, 30);
-        assert_eq!(queue.intervals.get(0).unwrap().to_id, 40);
+        assert_eq!(queue.intervals.first().unwrap().from_id, 30);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 40);
 
         assert_eq!(queue.intervals.get(1).unwrap().from_id, 50);
         assert_eq!(queue.intervals.get(1).unwrap().to_id, 60);
@@ -1079,8 +1103,8 @@ mod tests_left_is_joining_to_from_id {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 2);
 
-        assert_eq!(queue.intervals.get(0).unwrap().from_id, 50);
-        assert_eq!(queue.intervals.get(0).unwrap().to_id, 60);
+        assert_eq!(queue.intervals.first().unwrap().from_id, 50);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 60);
 
         assert_eq!(queue.intervals.get(1).unwrap().from_id, 70);
         assert_eq!(queue.intervals.get(1).unwrap().to_id, 80);
@@ -1106,8 +1130,8 @@ mod tests_left_is_joining_to_to_id {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 3);
 
-        assert_eq!(queue.intervals.get(0).unwrap().from_id, 10);
-        assert_eq!(queue.intervals.get(0).unwrap().to_id, 20);
+        assert_eq!(queue.intervals.first().unwrap().from_id, 10);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 20);
 
         assert_eq!(queue.intervals.get(1).unwrap().from_id, 50);
         assert_eq!(queue.intervals.get(1).unwrap().to_id, 60);
@@ -1129,8 +1153,8 @@ mod tests_left_is_joining_to_to_id {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 2);
 
-        assert_eq!(queue.intervals.get(0).unwrap().from_id, 10);
-        assert_eq!(queue.intervals.get(0).unwrap().to_id, 20);
+        assert_eq!(queue.intervals.first().unwrap().from_id, 10);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 20);
 
         assert_eq!(queue.intervals.get(1).unwrap().from_id, 70);
         assert_eq!(queue.intervals.get(1).unwrap().to_id, 80);
@@ -1149,8 +1173,8 @@ mod tests_left_is_joining_to_to_id {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 4);
 
-        assert_eq!(queue.intervals.get(0).unwrap().from_id, 10);
-        assert_eq!(queue.intervals.get(0).unwrap().to_id, 20);
+        assert_eq!(queue.intervals.first().unwrap().from_id, 10);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 20);
 
         assert_eq!(queue.intervals.get(1).unwrap().from_id, 31);
         assert_eq!(queue.intervals.get(1).unwrap().to_id, 40);
@@ -1175,8 +1199,8 @@ mod tests_left_is_joining_to_to_id {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 3);
 
-        assert_eq!(queue.intervals.get(0).unwrap().from_id, 10);
-        assert_eq!(queue.intervals.get(0).unwrap().to_id, 20);
+        assert_eq!(queue.intervals.first().unwrap().from_id, 10);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 20);
 
         assert_eq!(queue.intervals.get(1).unwrap().from_id, 50);
         assert_eq!(queue.intervals.get(1).unwrap().to_id, 60);
@@ -1198,8 +1222,8 @@ mod tests_left_is_joining_to_to_id {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 3);
 
-        assert_eq!(queue.intervals.get(0).unwrap().from_id, 10);
-        assert_eq!(queue.intervals.get(0).unwrap().to_id, 20);
+        assert_eq!(queue.intervals.first().unwrap().from_id, 10);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 20);
 
         assert_eq!(queue.intervals.get(1).unwrap().from_id, 54);
         assert_eq!(queue.intervals.get(1).unwrap().to_id, 60);
@@ -1221,8 +1245,8 @@ mod tests_left_is_joining_to_to_id {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 2);
 
-        assert_eq!(queue.intervals.get(0).unwrap().from_id, 10);
-        assert_eq!(queue.intervals.get(0).unwrap().to_id, 20);
+        assert_eq!(queue.intervals.first().unwrap().from_id, 10);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 20);
 
         assert_eq!(queue.intervals.get(1).unwrap().from_id, 70);
         assert_eq!(queue.intervals.get(1).unwrap().to_id, 80);
@@ -1241,8 +1265,8 @@ mod tests_left_is_joining_to_to_id {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 1);
 
-        assert_eq!(queue.intervals.get(0).unwrap().from_id, 10);
-        assert_eq!(queue.intervals.get(0).unwrap().to_id, 20);
+        assert_eq!(queue.intervals.first().unwrap().from_id, 10);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 20);
     }
 
     #[test]
@@ -1258,8 +1282,8 @@ mod tests_left_is_joining_to_to_id {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 1);
 
-        assert_eq!(queue.intervals.get(0).unwrap().from_id, 10);
-        assert_eq!(queue.intervals.get(0).unwrap().to_id, 20);
+        assert_eq!(queue.intervals.first().unwrap().from_id, 10);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 20);
     }
 
     #[test]
@@ -1275,8 +1299,8 @@ mod tests_left_is_joining_to_to_id {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 3);
 
-        assert_eq!(queue.intervals.get(0).unwrap().from_id, 10);
-        assert_eq!(queue.intervals.get(0).unwrap().to_id, 20);
+        assert_eq!(queue.intervals.first().unwrap().from_id, 10);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 20);
 
         assert_eq!(queue.intervals.get(1).unwrap().from_id, 42);
         assert_eq!(queue.intervals.get(1).unwrap().to_id, 60);
@@ -1298,8 +1322,8 @@ mod tests_left_is_joining_to_to_id {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 2);
 
-        assert_eq!(queue.intervals.get(0).unwrap().from_id, 10);
-        assert_eq!(queue.intervals.get(0).unwrap().to_id, 20);
+        assert_eq!(queue.intervals.first().unwrap().from_id, 10);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 20);
 
         assert_eq!(queue.intervals.get(1).unwrap().from_id, 62);
         assert_eq!(queue.intervals.get(1).unwrap().to_id, 80);
@@ -1318,8 +1342,8 @@ mod tests_left_is_joining_to_to_id {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 3);
 
-        assert_eq!(queue.intervals.get(0).unwrap().from_id, 10);
-        assert_eq!(queue.intervals.get(0).unwrap().to_id, 20);
+        assert_eq!(queue.intervals.first().unwrap().from_id, 10);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 20);
 
         assert_eq!(queue.intervals.get(1).unwrap().from_id, 50);
         assert_eq!(queue.intervals.get(1).unwrap().to_id, 60);
@@ -1341,8 +1365,8 @@ mod tests_left_is_joining_to_to_id {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 2);
 
-        assert_eq!(queue.intervals.get(0).unwrap().from_id, 10);
-        assert_eq!(queue.intervals.get(0).unwrap().to_id, 20);
+        assert_eq!(queue.intervals.first().unwrap().from_id, 10);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 20);
 
         assert_eq!(queue.intervals.get(1).unwrap().from_id, 70);
         assert_eq!(queue.intervals.get(1).unwrap().to_id, 80);
@@ -1368,8 +1392,8 @@ mod tests_left_is_first_exact {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 3);
 
-        assert_eq!(queue.intervals.get(0).unwrap().from_id, 10);
-        assert_eq!(queue.intervals.get(0).unwrap().to_id, 20);
+        assert_eq!(queue.intervals.first().unwrap().from_id, 10);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 14);
 
         assert_eq!(queue.intervals.get(1).unwrap().from_id, 50);
         assert_eq!(queue.intervals.get(1).unwrap().to_id, 60);
@@ -1391,8 +1415,8 @@ mod tests_left_is_first_exact {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 2);
 
-        assert_eq!(queue.intervals.get(0).unwrap().from_id, 10);
-        assert_eq!(queue.intervals.get(0).unwrap().to_id, 20);
+        assert_eq!(queue.intervals.first().unwrap().from_id, 10);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 14);
 
         assert_eq!(queue.intervals.get(1).unwrap().from_id, 70);
         assert_eq!(queue.intervals.get(1).unwrap().to_id, 80);
@@ -1411,8 +1435,8 @@ mod tests_left_is_first_exact {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 4);
 
-        assert_eq!(queue.intervals.get(0).unwrap().from_id, 10);
-        assert_eq!(queue.intervals.get(0).unwrap().to_id, 20);
+        assert_eq!(queue.intervals.first().unwrap().from_id, 10);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 14);
 
         assert_eq!(queue.intervals.get(1).unwrap().from_id, 31);
         assert_eq!(queue.intervals.get(1).unwrap().to_id, 40);
@@ -1437,8 +1461,8 @@ mod tests_left_is_first_exact {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 3);
 
-        assert_eq!(queue.intervals.get(0).unwrap().from_id, 10);
-        assert_eq!(queue.intervals.get(0).unwrap().to_id, 20);
+        assert_eq!(queue.intervals.first().unwrap().from_id, 10);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 14);
 
         assert_eq!(queue.intervals.get(1).unwrap().from_id, 50);
         assert_eq!(queue.intervals.get(1).unwrap().to_id, 60);
@@ -1460,8 +1484,8 @@ mod tests_left_is_first_exact {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 3);
 
-        assert_eq!(queue.intervals.get(0).unwrap().from_id, 10);
-        assert_eq!(queue.intervals.get(0).unwrap().to_id, 20);
+        assert_eq!(queue.intervals.first().unwrap().from_id, 10);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 14);
 
         assert_eq!(queue.intervals.get(1).unwrap().from_id, 54);
         assert_eq!(queue.intervals.get(1).unwrap().to_id, 60);
@@ -1483,8 +1507,8 @@ mod tests_left_is_first_exact {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 2);
 
-        assert_eq!(queue.intervals.get(0).unwrap().from_id, 10);
-        assert_eq!(queue.intervals.get(0).unwrap().to_id, 20);
+        assert_eq!(queue.intervals.first().unwrap().from_id, 10);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 14);
 
         assert_eq!(queue.intervals.get(1).unwrap().from_id, 70);
         assert_eq!(queue.intervals.get(1).unwrap().to_id, 80);
@@ -1503,8 +1527,8 @@ mod tests_left_is_first_exact {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 1);
 
-        assert_eq!(queue.intervals.get(0).unwrap().from_id, 10);
-        assert_eq!(queue.intervals.get(0).unwrap().to_id, 20);
+        assert_eq!(queue.intervals.first().unwrap().from_id, 10);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 14);
     }
 
     #[test]
@@ -1520,8 +1544,8 @@ mod tests_left_is_first_exact {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 1);
 
-        assert_eq!(queue.intervals.get(0).unwrap().from_id, 10);
-        assert_eq!(queue.intervals.get(0).unwrap().to_id, 20);
+        assert_eq!(queue.intervals.first().unwrap().from_id, 10);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 14);
     }
 
     #[test]
@@ -1537,8 +1561,8 @@ mod tests_left_is_first_exact {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 3);
 
-        assert_eq!(queue.intervals.get(0).unwrap().from_id, 10);
-        assert_eq!(queue.intervals.get(0).unwrap().to_id, 20);
+        assert_eq!(queue.intervals.first().unwrap().from_id, 10);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 14);
 
         assert_eq!(queue.intervals.get(1).unwrap().from_id, 42);
         assert_eq!(queue.intervals.get(1).unwrap().to_id, 60);
@@ -1560,8 +1584,8 @@ mod tests_left_is_first_exact {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 2);
 
-        assert_eq!(queue.intervals.get(0).unwrap().from_id, 10);
-        assert_eq!(queue.intervals.get(0).unwrap().to_id, 20);
+        assert_eq!(queue.intervals.first().unwrap().from_id, 10);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 14);
 
         assert_eq!(queue.intervals.get(1).unwrap().from_id, 62);
         assert_eq!(queue.intervals.get(1).unwrap().to_id, 80);
@@ -1580,8 +1604,8 @@ mod tests_left_is_first_exact {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 3);
 
-        assert_eq!(queue.intervals.get(0).unwrap().from_id, 10);
-        assert_eq!(queue.intervals.get(0).unwrap().to_id, 20);
+        assert_eq!(queue.intervals.first().unwrap().from_id, 10);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 14);
 
         assert_eq!(queue.intervals.get(1).unwrap().from_id, 50);
         assert_eq!(queue.intervals.get(1).unwrap().to_id, 60);
@@ -1603,10 +1627,166 @@ mod tests_left_is_first_exact {
         queue.remove_range(&range_to_remove);
         assert_eq!(queue.intervals.len(), 2);
 
-        assert_eq!(queue.intervals.get(0).unwrap().from_id, 10);
-        assert_eq!(queue.intervals.get(0).unwrap().to_id, 20);
+        assert_eq!(queue.intervals.first().unwrap().from_id, 10);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 14);
 
         assert_eq!(queue.intervals.get(1).unwrap().from_id, 70);
         assert_eq!(queue.intervals.get(1).unwrap().to_id, 80);
     }
+
+    #[test]
+    fn test_right_exact_same_interval_splits_it() {
+        let mut queue = QueueWithIntervals::new();
+
+        queue.enqueue_range(QueueIndexRange::restore(10, 20));
+        queue.enqueue_range(QueueIndexRange::restore(30, 40));
+
+        let range_to_remove = QueueIndexRange::restore(FROM_ID, 17);
+        queue.remove_range(&range_to_remove);
+        assert_eq!(queue.intervals.len(), 3);
+
+        assert_eq!(queue.intervals.first().unwrap().from_id, 10);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 14);
+
+        assert_eq!(queue.intervals.get(1).unwrap().from_id, 18);
+        assert_eq!(queue.intervals.get(1).unwrap().to_id, 20);
+
+        assert_eq!(queue.intervals.get(2).unwrap().from_id, 30);
+        assert_eq!(queue.intervals.get(2).unwrap().to_id, 40);
+    }
+
+    #[test]
+    fn test_single_value_after_interval_is_noop() {
+        let mut queue = QueueWithIntervals::new();
+
+        queue.enqueue_range(QueueIndexRange::restore(10, 20));
+        queue.enqueue_range(QueueIndexRange::restore(30, 40));
+
+        queue.remove_range(QueueIndexRange::restore(21, 21));
+        assert_eq!(queue.intervals.len(), 2);
+
+        assert_eq!(queue.intervals.first().unwrap().from_id, 10);
+        assert_eq!(queue.intervals.first().unwrap().to_id, 20);
+
+        assert_eq!(queue.intervals.get(1).unwrap().from_id, 30);
+        assert_eq!(queue.intervals.get(1).unwrap().to_id, 40);
+    }
+
+    #[test]
+    fn test_covering_single_interval_leaves_empty_queue() {
+        let mut queue = QueueWithIntervals::new();
+
+        queue.enqueue_range(QueueIndexRange::restore(10, 20));
+
+        queue.remove_range(QueueIndexRange::restore(5, 21));
+
+        assert!(queue.is_empty());
+        assert_eq!(queue.intervals.len(), 1);
+    }
+}
+
+#[cfg(test)]
+mod tests_removed_values {
+    use super::{QueueIndexRange, QueueWithIntervals};
+
+    fn pieces(queue: &mut QueueWithIntervals<i64>, from_id: i64, to_id: i64) -> Vec<(i64, i64)> {
+        queue
+            .remove_range_pieces(QueueIndexRange::restore(from_id, to_id))
+            .into_iter()
+            .map(|itm| (itm.from_id, itm.to_id))
+            .collect()
+    }
+
+    #[test]
+    fn test_remove_range_count() {
+        let mut queue = QueueWithIntervals::new();
+
+        queue.enqueue_range(QueueIndexRange::restore(10, 20));
+        queue.enqueue_range(QueueIndexRange::restore(30, 40));
+
+        assert_eq!(
+            queue.remove_range_count(QueueIndexRange::restore(15, 35)),
+            12
+        );
+        assert_eq!(
+            queue.remove_range_count(QueueIndexRange::restore(15, 35)),
+            0
+        );
+        assert_eq!(queue.remove_range_count(QueueIndexRange::new_empty(10)), 0);
+        assert_eq!(
+            queue.remove_range_count(QueueIndexRange::restore(0, 100)),
+            10
+        );
+
+        assert!(queue.is_empty());
+        assert_eq!(
+            queue.remove_range_count(QueueIndexRange::restore(0, 100)),
+            0
+        );
+    }
+
+    #[test]
+    fn test_remove_range_count_of_all_values() {
+        let mut queue = QueueWithIntervals::<i128>::from_single_interval(i128::MIN, i128::MAX);
+
+        assert_eq!(
+            queue.remove_range_count(QueueIndexRange::new_full()),
+            usize::MAX
+        );
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_remove_range_pieces() {
+        let mut queue = QueueWithIntervals::new();
+
+        queue.enqueue_range(QueueIndexRange::restore(10, 20));
+        queue.enqueue_range(QueueIndexRange::restore(30, 40));
+        queue.enqueue_range(QueueIndexRange::restore(50, 60));
+
+        assert_eq!(
+            pieces(&mut queue, 15, 55),
+            vec![(15, 20), (30, 40), (50, 55)]
+        );
+        assert_eq!(pieces(&mut queue, 15, 55), vec![]);
+        assert_eq!(pieces(&mut queue, 0, 12), vec![(10, 12)]);
+
+        assert_eq!(queue.intervals.len(), 2);
+        assert_eq!(queue.len(), 7);
+
+        assert_eq!(
+            pieces(&mut queue, i64::MIN, i64::MAX),
+            vec![(13, 14), (56, 60)]
+        );
+        assert!(queue.is_empty());
+    }
+}
+
+#[cfg(test)]
+mod tests_std_ranges {
+    use crate::QueueWithIntervals;
+
+    #[test]
+    fn test_enqueue_and_remove_std_ranges() {
+        let mut queue = QueueWithIntervals::new();
+        queue.enqueue_range(10..=20);
+        queue.enqueue_range(30..40);
+        assert_eq!(queue.to_string(), "10-20,30-39");
+
+        queue.remove_range(15..32);
+        assert_eq!(queue.to_string(), "10-14,32-39");
+
+        assert_eq!(queue.remove_range_count(..=11), 2);
+        assert_eq!(queue.to_string(), "12-14,32-39");
+
+        queue.remove_range(35..);
+        assert_eq!(queue.to_string(), "12-14,32-34");
+
+        let mut queue = QueueWithIntervals::<u8>::new();
+        queue.enqueue_range(..);
+        assert_eq!(queue.len(), 256);
+
+        queue.remove_range(..);
+        assert!(queue.is_empty());
+    }
 }
@@ -0,0 +1,99 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error};
+
+use super::{QueueIndexRange, QueueWithIntervals, invariants::check_intervals};
+use crate::QueueValue;
+
+// Ranges are serialized as [from_id, to_id] pairs
+impl<T: QueueValue + Serialize> Serialize for QueueIndexRange<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (self.from_id, self.to_id).serialize(serializer)
+    }
+}
+
+impl<'de, T: QueueValue + Deserialize<'de>> Deserialize<'de> for QueueIndexRange<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (from_id, to_id) = <(T, T)>::deserialize(deserializer)?;
+        Ok(Self { from_id, to_id })
+    }
+}
+
+// Queues are serialized as a list of [from_id, to_id] pairs. An empty queue is an empty list
+impl<T: QueueValue + Serialize> Serialize for QueueWithIntervals<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.intervals_iter())
+    }
+}
+
+impl<'de, T: QueueValue + Deserialize<'de>> Deserialize<'de> for QueueWithIntervals<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let intervals = Vec::<QueueIndexRange<T>>::deserialize(deserializer)?;
+
+        check_intervals(&intervals).map_err(D::Error::custom)?;
+
+        Ok(Self::from_sorted_intervals(intervals))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{QueueIndexRange, QueueWithIntervals};
+
+    #[test]
+    fn test_serialize() {
+        let mut queue = QueueWithIntervals::new();
+        queue.enqueue_range(QueueIndexRange::restore(1, 5));
+        queue.enqueue(7);
+        queue.enqueue_range(QueueIndexRange::restore(-12, -9));
+
+        assert_eq!(
+            serde_json::to_string(&queue).unwrap(),
+            "[[-12,-9],[1,5],[7,7]]"
+        );
+
+        assert_eq!(
+            serde_json::to_string(&QueueIndexRange::restore(3u8, 4)).unwrap(),
+            "[3,4]"
+        );
+
+        let mut queue = QueueWithIntervals::<u8>::new();
+        assert_eq!(serde_json::to_string(&queue).unwrap(), "[]");
+
+        queue.enqueue(10);
+        queue.clean();
+        assert_eq!(serde_json::to_string(&queue).unwrap(), "[]");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut queue = QueueWithIntervals::<u64>::new();
+        queue.enqueue_range(QueueIndexRange::restore(0, 5));
+        queue.enqueue_range(QueueIndexRange::restore(100, 200));
+        queue.enqueue(u64::MAX);
+
+        let json = serde_json::to_string(&queue).unwrap();
+        let restored: QueueWithIntervals<u64> = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(restored.get_snapshot().len(), 3);
+        assert_eq!(restored.len(), queue.len());
+        assert_eq!(serde_json::to_string(&restored).unwrap(), json);
+
+        let restored: QueueWithIntervals<u64> = serde_json::from_str("[]").unwrap();
+        assert!(restored.is_empty());
+    }
+
+    #[test]
+    fn test_deserialize_rejects_non_canonical_input() {
+        fn error(json: &str) -> String {
+            serde_json::from_str::<QueueWithIntervals<i64>>(json)
+                .unwrap_err()
+                .to_string()
+        }
+
+        assert!(error("[[5,1]]").contains("is empty"));
+        assert!(error("[[10,20],[1,5]]").contains("before the previous interval"));
+        assert!(error("[[1,10],[5,20]]").contains("overlaps the previous interval"));
+        assert!(error("[[1,5],[5,6]]").contains("overlaps the previous interval"));
+        assert!(error("[[1,5],[6,10]]").contains("right after the previous interval"));
+        assert!(error("[[1,5,7]]").contains("trailing"));
+    }
+}
@@ -0,0 +1,400 @@
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Sub, SubAssign};
+
+use super::{QueueIndexRange, QueueWithIntervals};
+use crate::QueueValue;
+
+impl<T: QueueValue> QueueWithIntervals<T> {
+    /// Returns values which are in self or in other
+    pub fn union(&self, other: &Self) -> Self {
+        Self::from_sorted_intervals(union_intervals(
+            self.get_non_empty_intervals(),
+            other.get_non_empty_intervals(),
+        ))
+    }
+
+    /// Returns values which are both in self and in other
+    pub fn intersection(&self, other: &Self) -> Self {
+        Self::from_sorted_intervals(intersect_intervals(
+            self.get_non_empty_intervals(),
+            other.get_non_empty_intervals(),
+        ))
+    }
+
+    /// Returns values which are in self but not in other
+    pub fn difference(&self, other: &Self) -> Self {
+        Self::from_sorted_intervals(subtract_intervals(
+            self.get_non_empty_intervals(),
+            other.get_non_empty_intervals(),
+        ))
+    }
+
+    /// Returns values which are either in self or in other, but not in both
+    pub fn symmetric_difference(&self, other: &Self) -> Self {
+        Self::from_sorted_intervals(symmetric_difference_intervals(
+            self.get_non_empty_intervals(),
+            other.get_non_empty_intervals(),
+        ))
+    }
+
+    pub fn union_with(&mut self, other: &Self) {
+        let result = union_intervals(
+            self.get_non_empty_intervals(),
+            other.get_non_empty_intervals(),
+        );
+        self.set_sorted_intervals(result);
+    }
+
+    pub fn intersection_with(&mut self, other: &Self) {
+        let result = intersect_intervals(
+            self.get_non_empty_intervals(),
+            other.get_non_empty_intervals(),
+        );
+        self.set_sorted_intervals(result);
+    }
+
+    pub fn difference_with(&mut self, other: &Self) {
+        let result = subtract_intervals(
+            self.get_non_empty_intervals(),
+            other.get_non_empty_intervals(),
+        );
+        self.set_sorted_intervals(result);
+    }
+
+    pub fn symmetric_difference_with(&mut self, other: &Self) {
+        let result = symmetric_difference_intervals(
+            self.get_non_empty_intervals(),
+            other.get_non_empty_intervals(),
+        );
+        self.set_sorted_intervals(result);
+    }
+}
+
+// Appends the interval joining it with the last one if they overlap or are adjacent.
+// Intervals must come in order of from_id
+pub(crate) fn push_interval<T: QueueValue>(
+    result: &mut Vec<QueueIndexRange<T>>,
+    interval: &QueueIndexRange<T>,
+) {
+    if let Some(last) = result.last_mut() {
+        let joins = match last.to_id.checked_add_one() {
+            Some(to_plus_one) => interval.from_id <= to_plus_one,
+            None => true,
+        };
+
+        if joins {
+            if interval.to_id > last.to_id {
+                last.to_id = interval.to_id;
+            }
+            return;
+        }
+    }
+
+    result.push(interval.clone());
+}
+
+fn union_intervals<T: QueueValue>(
+    left: &[QueueIndexRange<T>],
+    right: &[QueueIndexRange<T>],
+) -> Vec<QueueIndexRange<T>> {
+    let mut result = Vec::with_capacity(left.len() + right.len());
+
+    let mut left = left.iter().peekable();
+    let mut right = right.iter().peekable();
+
+    loop {
+        let next = match (left.peek(), right.peek()) {
+            (Some(l), Some(r)) => {
+                if l.from_id <= r.from_id {
+                    left.next()
+                } else {
+                    right.next()
+                }
+            }
+            (Some(_), None) => left.next(),
+            (None, Some(_)) => right.next(),
+            (None, None) => break,
+        };
+
+        push_interval(&mut result, next.unwrap());
+    }
+
+    result
+}
+
+fn intersect_intervals<T: QueueValue>(
+    left: &[QueueIndexRange<T>],
+    right: &[QueueIndexRange<T>],
+) -> Vec<QueueIndexRange<T>> {
+    let mut result = Vec::new();
+
+    let mut left_index = 0;
+    let mut right_index = 0;
+
+    while let (Some(l), Some(r)) = (left.get(left_index), right.get(right_index)) {
+        let overlap = l.clip(r.from_id, r.to_id);
+        if !overlap.is_empty() {
+            result.push(overlap);
+        }
+
+        if l.to_id < r.to_id {
+            left_index += 1;
+        } else {
+            right_index += 1;
+        }
+    }
+
+    result
+}
+
+fn subtract_intervals<T: QueueValue>(
+    left: &[QueueIndexRange<T>],
+    right: &[QueueIndexRange<T>],
+) -> Vec<QueueIndexRange<T>> {
+    let mut result = Vec::with_capacity(left.len());
+
+    let mut right_index = 0;
+
+    for interval in left {
+        while let Some(r) = right.get(right_index) {
+            if r.to_id >= interval.from_id {
+                break;
+            }
+            right_index += 1;
+        }
+
+        let mut from_id = Some(interval.from_id);
+
+        while let (Some(current_from_id), Some(r)) = (from_id, right.get(right_index)) {
+            if r.from_id > interval.to_id {
+                break;
+            }
+
+            if r.from_id > current_from_id {
+                result.push(QueueIndexRange {
+                    from_id: current_from_id,
+                    to_id: r.from_id - T::one(),
+                });
+            }
+
+            if r.to_id >= interval.to_id {
+                // The rest of the interval is covered. Keep r since it can cover the next interval as well
+                from_id = None;
+            } else {
+                from_id = Some(r.to_id + T::one());
+                right_index += 1;
+            }
+        }
+
+        if let Some(from_id) = from_id {
+            result.push(QueueIndexRange {
+                from_id,
+                to_id: interval.to_id,
+            });
+        }
+    }
+
+    result
+}
+
+fn symmetric_difference_intervals<T: QueueValue>(
+    left: &[QueueIndexRange<T>],
+    right: &[QueueIndexRange<T>],
+) -> Vec<QueueIndexRange<T>> {
+    union_intervals(
+        &subtract_intervals(left, right),
+        &subtract_intervals(right, left),
+    )
+}
+
+impl<T: QueueValue> BitOr<&QueueWithIntervals<T>> for &QueueWithIntervals<T> {
+    type Output = QueueWithIntervals<T>;
+
+    fn bitor(self, rhs: &QueueWithIntervals<T>) -> QueueWithIntervals<T> {
+        self.union(rhs)
+    }
+}
+
+impl<T: QueueValue> BitAnd<&QueueWithIntervals<T>> for &QueueWithIntervals<T> {
+    type Output = QueueWithIntervals<T>;
+
+    fn bitand(self, rhs: &QueueWithIntervals<T>) -> QueueWithIntervals<T> {
+        self.intersection(rhs)
+    }
+}
+
+impl<T: QueueValue> Sub<&QueueWithIntervals<T>> for &QueueWithIntervals<T> {
+    type Output = QueueWithIntervals<T>;
+
+    fn sub(self, rhs: &QueueWithIntervals<T>) -> QueueWithIntervals<T> {
+        self.difference(rhs)
+    }
+}
+
+impl<T: QueueValue> BitXor<&QueueWithIntervals<T>> for &QueueWithIntervals<T> {
+    type Output = QueueWithIntervals<T>;
+
+    fn bitxor(self, rhs: &QueueWithIntervals<T>) -> QueueWithIntervals<T> {
+        self.symmetric_difference(rhs)
+    }
+}
+
+impl<T: QueueValue> BitOrAssign<&QueueWithIntervals<T>> for QueueWithIntervals<T> {
+    fn bitor_assign(&mut self, rhs: &QueueWithIntervals<T>) {
+        self.union_with(rhs);
+    }
+}
+
+impl<T: QueueValue> BitAndAssign<&QueueWithIntervals<T>> for QueueWithIntervals<T> {
+    fn bitand_assign(&mut self, rhs: &QueueWithIntervals<T>) {
+        self.intersection_with(rhs);
+    }
+}
+
+impl<T: QueueValue> SubAssign<&QueueWithIntervals<T>> for QueueWithIntervals<T> {
+    fn sub_assign(&mut self, rhs: &QueueWithIntervals<T>) {
+        self.difference_with(rhs);
+    }
+}
+
+impl<T: QueueValue> BitXorAssign<&QueueWithIntervals<T>> for QueueWithIntervals<T> {
+    fn bitxor_assign(&mut self, rhs: &QueueWithIntervals<T>) {
+        self.symmetric_difference_with(rhs);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{QueueIndexRange, QueueWithIntervals};
+
+    fn queue(intervals: &[(i64, i64)]) -> QueueWithIntervals<i64> {
+        QueueWithIntervals::restore(
+            intervals
+                .iter()
+                .map(|(from_id, to_id)| QueueIndexRange::restore(*from_id, *to_id))
+                .collect(),
+        )
+    }
+
+    fn intervals(queue: &QueueWithIntervals<i64>) -> Vec<(i64, i64)> {
+        queue
+            .intervals_iter()
+            .map(|itm| (itm.from_id, itm.to_id))
+            .collect()
+    }
+
+    #[test]
+    fn test_union() {
+        let left = queue(&[(1, 5), (10, 20), (40, 50)]);
+        let right = queue(&[(3, 8), (21, 25), (30, 35), (60, 60)]);
+
+        let result = left.union(&right);
+        assert_eq!(
+            vec![(1, 8), (10, 25), (30, 35), (40, 50), (60, 60)],
+            intervals(&result)
+        );
+        assert_eq!(42, result.len());
+
+        assert_eq!(intervals(&result), intervals(&(&left | &right)));
+    }
+
+    #[test]
+    fn test_intersection() {
+        let left = queue(&[(1, 5), (10, 20), (40, 50)]);
+        let right = queue(&[(3, 12), (15, 16), (18, 45)]);
+
+        let result = left.intersection(&right);
+        assert_eq!(
+            vec![(3, 5), (10, 12), (15, 16), (18, 20), (40, 45)],
+            intervals(&result)
+        );
+        assert_eq!(17, result.len());
+
+        assert_eq!(intervals(&result), intervals(&(&left & &right)));
+    }
+
+    #[test]
+    fn test_difference() {
+        let delivered = queue(&[(1, 20), (30, 40)]);
+        let acked = queue(&[(0, 3), (5, 5), (10, 12), (18, 32), (40, 45)]);
+
+        let result = delivered.difference(&acked);
+        assert_eq!(vec![(4, 4), (6, 9), (13, 17), (33, 39)], intervals(&result));
+        assert_eq!(17, result.len());
+
+        assert_eq!(intervals(&result), intervals(&(&delivered - &acked)));
+    }
+
+    #[test]
+    fn test_symmetric_difference() {
+        let left = queue(&[(1, 10), (20, 30)]);
+        let right = queue(&[(5, 25)]);
+
+        let result = left.symmetric_difference(&right);
+        assert_eq!(vec![(1, 4), (11, 19), (26, 30)], intervals(&result));
+        assert_eq!(18, result.len());
+
+        assert_eq!(intervals(&result), intervals(&(&left ^ &right)));
+    }
+
+    #[test]
+    fn test_with_empty_queue() {
+        let left = queue(&[(1, 10)]);
+        let empty = QueueWithIntervals::new();
+
+        assert_eq!(vec![(1, 10)], intervals(&left.union(&empty)));
+        assert_eq!(vec![(1, 10)], intervals(&empty.union(&left)));
+        assert!(left.intersection(&empty).is_empty());
+        assert_eq!(vec![(1, 10)], intervals(&left.difference(&empty)));
+        assert!(empty.difference(&left).is_empty());
+        assert_eq!(vec![(1, 10)], intervals(&left.symmetric_difference(&empty)));
+        assert!(left.difference(&left).is_empty());
+    }
+
+    #[test]
+    fn test_in_place_operations() {
+        let other = queue(&[(5, 15)]);
+
+        let mut result = queue(&[(1, 10)]);
+        result.union_with(&other);
+        assert_eq!(vec![(1, 15)], intervals(&result));
+        assert_eq!(15, result.len());
+
+        let mut result = queue(&[(1, 10)]);
+        result &= &other;
+        assert_eq!(vec![(5, 10)], intervals(&result));
+        assert_eq!(6, result.len());
+
+        let mut result = queue(&[(1, 10)]);
+        result -= &other;
+        assert_eq!(vec![(1, 4)], intervals(&result));
+        assert_eq!(4, result.len());
+
+        let mut result = queue(&[(1, 10)]);
+        result ^= &other;
+        assert_eq!(vec![(1, 4), (11, 15)], intervals(&result));
+        assert_eq!(9, result.len());
+
+        let mut result = queue(&[(1, 10)]);
+        result.difference_with(&queue(&[(0, 20)]));
+        assert!(result.is_empty());
+        assert_eq!(0, result.len());
+    }
+
+    #[test]
+    fn test_at_numeric_boundaries() {
+        let left = QueueWithIntervals::from_single_interval(0u8, 255);
+        let right = QueueWithIntervals::restore(vec![
+            QueueIndexRange::restore(0u8, 0),
+            QueueIndexRange::restore(255u8, 255),
+        ]);
+
+        let result = left.difference(&right);
+        assert_eq!(1, result.get_intervals().len());
+        assert_eq!(1, result.get_intervals()[0].from_id);
+        assert_eq!(254, result.get_intervals()[0].to_id);
+
+        let result = right.union(&result);
+        assert_eq!(1, result.get_intervals().len());
+        assert_eq!(256, result.len());
+    }
+}
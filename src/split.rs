@@ -0,0 +1,138 @@
+use super::QueueWithIntervals;
+use crate::QueueValue;
+
+impl<T: QueueValue> QueueWithIntervals<T> {
+    /// Moves every value which is greater or equal to at into the returned queue
+    pub fn split_off(&mut self, at: T) -> Self {
+        match at.checked_sub_one() {
+            Some(below) => {
+                let below = self.dequeue_up_to(below);
+                std::mem::replace(self, below)
+            }
+            None => std::mem::take(self),
+        }
+    }
+
+    /// Keeps the first n values and moves the rest into the returned queue
+    pub fn split_at_count(&mut self, n: usize) -> Self {
+        match self.nth(n) {
+            Some(at) => self.split_off(at),
+            None => Self::new(),
+        }
+    }
+
+    /// Moves every value of the other queue into this one, leaving the other queue empty
+    pub fn append(&mut self, other: &mut Self) {
+        if other.is_empty() {
+            return;
+        }
+
+        if self.is_empty() {
+            std::mem::swap(self, other);
+            return;
+        }
+
+        // Intervals of the other queue go after ours, so only the edge intervals may need to be joined
+        let other_from_id = other.intervals.first().unwrap().from_id;
+        let last = self.intervals.last_mut().unwrap();
+
+        if last.to_id < other_from_id {
+            let mut other_intervals = std::mem::take(&mut other.intervals).into_iter();
+
+            if last.to_id.checked_add_one() == Some(other_from_id) {
+                last.to_id = other_intervals.next().unwrap().to_id;
+            }
+
+            self.intervals.extend(other_intervals);
+            self.values_count = self.values_count.wrapping_add(other.values_count);
+            self.after_mutation("append");
+
+            *other = Self::new();
+            return;
+        }
+
+        self.union_with(other);
+        other.clean();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::QueueWithIntervals;
+
+    #[test]
+    fn test_split_off() {
+        let mut queue: QueueWithIntervals = "1-10,20-25,30".parse().unwrap();
+
+        let above = queue.split_off(5);
+        assert_eq!(queue.to_string(), "1-4");
+        assert_eq!(above.to_string(), "5-10,20-25,30");
+        assert_eq!(queue.len(), 4);
+        assert_eq!(above.len(), 13);
+
+        let mut queue = above;
+        let above = queue.split_off(15);
+        assert_eq!(queue.to_string(), "5-10");
+        assert_eq!(above.to_string(), "20-25,30");
+
+        let above = queue.split_off(100);
+        assert_eq!(queue.to_string(), "5-10");
+        assert!(above.is_empty());
+
+        let above = queue.split_off(i64::MIN);
+        assert!(queue.is_empty());
+        assert_eq!(above.to_string(), "5-10");
+        assert!(queue.validate().is_ok());
+    }
+
+    #[test]
+    fn test_split_at_count() {
+        let mut queue: QueueWithIntervals = "1-3,10-15".parse().unwrap();
+
+        let rest = queue.split_at_count(5);
+        assert_eq!(queue.to_string(), "1-3,10-11");
+        assert_eq!(rest.to_string(), "12-15");
+
+        let rest = queue.split_at_count(5);
+        assert_eq!(queue.len(), 5);
+        assert!(rest.is_empty());
+
+        let rest = queue.split_at_count(0);
+        assert!(queue.is_empty());
+        assert_eq!(rest.to_string(), "1-3,10-11");
+    }
+
+    #[test]
+    fn test_append() {
+        let mut queue: QueueWithIntervals = "1-5".parse().unwrap();
+
+        // Adjacent to the last interval
+        let mut other: QueueWithIntervals = "6-8,20-22".parse().unwrap();
+        queue.append(&mut other);
+        assert_eq!(queue.to_string(), "1-8,20-22");
+        assert_eq!(queue.len(), 11);
+        assert!(other.is_empty());
+        assert!(other.validate().is_ok());
+
+        // Entirely above with a gap
+        let mut other: QueueWithIntervals = "30".parse().unwrap();
+        queue.append(&mut other);
+        assert_eq!(queue.to_string(), "1-8,20-22,30");
+
+        // Overlapping
+        let mut other: QueueWithIntervals = "0,7-21".parse().unwrap();
+        queue.append(&mut other);
+        assert_eq!(queue.to_string(), "0-22,30");
+        assert_eq!(queue.len(), 24);
+        assert!(other.is_empty());
+
+        let mut empty = QueueWithIntervals::new();
+        queue.append(&mut empty);
+        assert_eq!(queue.len(), 24);
+
+        empty.append(&mut queue);
+        assert_eq!(empty.to_string(), "0-22,30");
+        assert!(queue.is_empty());
+        assert!(empty.validate().is_ok());
+    }
+}